@@ -7,10 +7,10 @@ use syn::{
     parse::{Parse, Parser},
     spanned::Spanned,
     Attribute, Data, DataEnum, DeriveInput, Error, Field, Fields, GenericParam, Generics, Ident,
-    Variant,
+    Lit, Meta, NestedMeta, Type, Variant,
 };
 
-#[proc_macro_derive(Serializable, attributes(since))]
+#[proc_macro_derive(Serializable, attributes(since, binver))]
 pub fn derive_serializable(item: TokenStream) -> TokenStream {
     let input = match DeriveInput::parse.parse2(item.into()) {
         Ok(data) => data,
@@ -20,20 +20,24 @@ pub fn derive_serializable(item: TokenStream) -> TokenStream {
     };
 
     let ident = input.ident;
+    let container = match ContainerAttributes::parse(&input.attrs) {
+        Ok(container) => container,
+        Err(e) => return e.into_compile_error().into(),
+    };
 
     match input.data {
         Data::Struct(s) => match s.fields {
             Fields::Named(fields) => {
-                derive_serializable_struct_fields(ident, input.generics, fields.named)
+                derive_serializable_struct_fields(ident, input.generics, container, fields.named)
             }
             Fields::Unnamed(fields) => {
-                derive_serializable_struct_fields(ident, input.generics, fields.unnamed)
+                derive_serializable_struct_fields(ident, input.generics, container, fields.unnamed)
             }
             Fields::Unit => Error::new(s.struct_token.span, "Unit structs not supported")
                 .into_compile_error()
                 .into(),
         },
-        Data::Enum(e) => derive_serializable_enum(ident, input.generics, e),
+        Data::Enum(e) => derive_serializable_enum(ident, input.generics, container, e),
         Data::Union(_) => Error::new(ident.span(), "Unions not supported")
             .into_compile_error()
             .into(),
@@ -43,12 +47,17 @@ pub fn derive_serializable(item: TokenStream) -> TokenStream {
 fn derive_serializable_struct_fields<'a>(
     ident: Ident,
     generics: Generics,
+    container: ContainerAttributes,
     fields: impl IntoIterator<Item = Field>,
 ) -> TokenStream {
     let mut ser_impl = Vec::new();
     let mut de_impl = Vec::new();
     let mut idents = Vec::new();
+    let mut max_sizes = Vec::new();
+    let mut fingerprints = Vec::new();
     let mut highest_version = None;
+    let mut schema = format!("struct {}{}{{", ident, container.schema());
+    let read_version = container.read_version();
 
     let generics = match get_generic(generics) {
         Ok(g) => g,
@@ -58,41 +67,51 @@ fn derive_serializable_struct_fields<'a>(
     for f in fields {
         let ident = f.ident.unwrap();
         let ty = &f.ty;
-        ser_impl.push(quote! { self.#ident.serialize(writer)?; });
+        let attributes = match FieldAttributes::parse(ident.span(), &f.attrs) {
+            Ok(attributes) => attributes,
+            Err(e) => return e.into_compile_error().into(),
+        };
+        ser_impl.push(attributes.serialize(quote! { &self.#ident }));
+        max_sizes.push(attributes.max_size(ty));
+        let deserialize = attributes.deserialize(ty);
+        schema += &attributes.schema(&ident.to_string());
+        fingerprints.push(attributes.fingerprint(ty));
 
         let Version {
             major,
             minor,
             patch,
             ..
-        } = match parse_attribute(ident.span(), &f.attrs) {
-            Ok(version) => version,
-            Err(e) => return e.into_compile_error().into(),
-        };
+        } = attributes.since;
 
         highest_version = match (highest_version.take(), Version::new(major, minor, patch)) {
             (None, v) => Some(v),
             (Some(v1), v2) => Some(if v1 > v2 { v1 } else { v2 }),
         };
 
+        let present = attributes.present();
         de_impl.push(quote! {
-            let #ident = if version < ::binver::Version::new(#major, #minor, #patch) {
-                Default::default()
+            let #ident = if #present {
+                #deserialize
             } else {
-                <#ty as Serializable>::deserialize(reader)?
+                Default::default()
             };
         });
         idents.push(ident);
     }
+    schema += "}";
 
     (quote! {
         impl<'a> binver::Serializable<'a> for #ident #generics {
-            fn serialize(&self, writer: &mut dyn binver::Writer) -> binver::WriteResult {
+            const FINGERPRINT: u64 = binver::fingerprint_all(#schema, &[#(#fingerprints),*]);
+            const MAX_SERIALIZED_SIZE: Option<usize> = binver::max_size_sum(&[#(#max_sizes),*]);
+
+            fn serialize<W: binver::Writer + ?Sized>(&self, writer: &mut W) -> binver::WriteResult {
                 #(#ser_impl)*
                 Ok(())
             }
-            fn deserialize(reader: &mut dyn binver::Reader<'a>) -> binver::ReadResult<Self> {
-                let version = reader.version();
+            fn deserialize<R: binver::Reader<'a> + ?Sized>(reader: &mut R) -> binver::ReadResult<Self> {
+                let version = #read_version;
                 #(#de_impl)*
                 Ok(Self {
                     #(#idents, )*
@@ -103,10 +122,19 @@ fn derive_serializable_struct_fields<'a>(
     .into()
 }
 
-fn derive_serializable_enum(ident: Ident, generics: Generics, data: DataEnum) -> TokenStream {
+fn derive_serializable_enum(
+    ident: Ident,
+    generics: Generics,
+    container: ContainerAttributes,
+    data: DataEnum,
+) -> TokenStream {
     let mut ser_impl = Vec::new();
     let mut de_impl = Vec::new();
+    let mut max_sizes = Vec::new();
+    let mut fingerprints = Vec::new();
     let mut highest_version = None;
+    let mut schema = format!("enum {}{}{{", ident, container.schema());
+    let read_version = container.read_version();
 
     let generics = match get_generic(generics) {
         Ok(g) => g,
@@ -133,20 +161,35 @@ fn derive_serializable_enum(ident: Ident, generics: Generics, data: DataEnum) ->
     let mut last_version: Option<Version> = None;
     for (index, variant) in data.variants.into_iter().enumerate() {
         let ident = variant.ident.clone();
-        let version = match parse_attribute(ident.span(), &variant.attrs) {
-            Ok(version) => version,
+        let attributes = match FieldAttributes::parse(ident.span(), &variant.attrs) {
+            Ok(attributes) if attributes.compress || attributes.bytes || attributes.varint => {
+                return Error::new(
+                    ident.span(),
+                    "`compress`, `bytes` and `varint` can only be used on fields",
+                )
+                .into_compile_error()
+                .into()
+            }
+            Ok(attributes) => attributes,
             Err(e) => return e.into_compile_error().into(),
         };
+        let version = attributes.since.clone();
         highest_version = match (highest_version.take(), version.clone()) {
             (None, v) => Some(v),
             (Some(v1), v2) => Some(if v1 > v2 { v1 } else { v2 }),
         };
 
-        if let Some(last) = last_version.replace(version.clone()) {
-            if last > version {
+        // Variants are stored by their position, so variants that are only gated by a feature must also be added at the bottom.
+        // They are ordered as if they were added in the version of the variant above them.
+        let ordered_version = match (attributes.has_since, &last_version) {
+            (false, Some(last)) => last.clone(),
+            _ => version.clone(),
+        };
+        if let Some(last) = last_version.replace(ordered_version.clone()) {
+            if last > ordered_version {
                 return Error::new(
                     ident.span(),
-                    format!("New versions must be added at the bottom. Version {:?} must be lower in the code than {:?}", last, version)
+                    format!("New versions must be added at the bottom. Version {:?} must be lower in the code than {:?}", last, ordered_version)
                 ).into_compile_error().into();
             }
         }
@@ -154,26 +197,37 @@ fn derive_serializable_enum(ident: Ident, generics: Generics, data: DataEnum) ->
         match EnumVariantSerDeResult::construct(
             index as u16,
             variant,
-            version,
+            attributes,
             &mut highest_version,
+            &mut schema,
+            &mut fingerprints,
         ) {
-            Ok(EnumVariantSerDeResult { ser, de }) => {
+            Ok(EnumVariantSerDeResult { ser, de, max_size }) => {
                 ser_impl.push(ser);
                 de_impl.push(de);
+                max_sizes.push(max_size);
             }
             Err(e) => return e.into_compile_error().into(),
         }
     }
+    schema += "}";
 
     (quote! {
         impl<'a> binver::Serializable<'a> for #ident #generics {
-            fn serialize(&self, writer: &mut dyn binver::Writer) -> binver::WriteResult {
+            const FINGERPRINT: u64 = binver::fingerprint_all(#schema, &[#(#fingerprints),*]);
+            // The variant index is a u16
+            const MAX_SERIALIZED_SIZE: Option<usize> = binver::max_size_sum(&[
+                <u16 as binver::Serializable>::MAX_SERIALIZED_SIZE,
+                binver::max_size_max(&[#(#max_sizes),*]),
+            ]);
+
+            fn serialize<W: binver::Writer + ?Sized>(&self, writer: &mut W) -> binver::WriteResult {
                 match self {
                     #(#ser_impl)*
                 }
             }
-            fn deserialize(reader: &mut dyn binver::Reader<'a>) -> binver::ReadResult<Self> {
-                let version = reader.version();
+            fn deserialize<R: binver::Reader<'a> + ?Sized>(reader: &mut R) -> binver::ReadResult<Self> {
+                let version = #read_version;
                 let variant = u16::deserialize(reader)?;
                 Ok(match variant {
                     #(#de_impl)*
@@ -213,53 +267,66 @@ fn get_generic(generics: Generics) -> Result<proc_macro2::TokenStream, Error> {
 struct EnumVariantSerDeResult {
     pub ser: proc_macro2::TokenStream,
     pub de: proc_macro2::TokenStream,
+    pub max_size: proc_macro2::TokenStream,
 }
 
 impl EnumVariantSerDeResult {
     fn construct(
         index: u16,
         variant: Variant,
-        version: Version,
+        attributes: FieldAttributes,
         highest_version: &mut Option<Version>,
+        schema: &mut String,
+        fingerprints: &mut Vec<proc_macro2::TokenStream>,
     ) -> Result<Self, Error> {
         let ident = variant.ident;
-        let Version {
-            major,
-            minor,
-            patch,
-            ..
-        } = version;
+        *schema += &format!("{}@{}", ident, attributes.since);
+        if let Some(feature) = &attributes.feature {
+            *schema += &format!("+feature={}", feature);
+        }
+        if let Some((_, discriminant)) = &variant.discriminant {
+            *schema += &format!("={}", quote!(#discriminant)).replace(' ', "");
+        }
+        *schema += "(";
+        let present = attributes.present();
+        let check_feature = attributes.check_feature();
 
-        match variant.fields {
+        let result = match variant.fields {
             Fields::Named(fields) => {
                 // Enum::Variant { a: ty, b: ty }
                 let mut field_names = Vec::new();
                 let mut field_serialize = Vec::new();
                 let mut field_deserialize = Vec::new();
+                let mut field_max_sizes = Vec::new();
 
                 for field in fields.named {
                     let ident = field.ident.unwrap();
                     let ty = field.ty;
+                    let attributes = FieldAttributes::parse(ident.span(), &field.attrs)?;
+                    let serialize = attributes.serialize(quote! { #ident });
+                    let deserialize = attributes.deserialize(&ty);
+                    *schema += &attributes.schema(&ident.to_string());
+                    fingerprints.push(attributes.fingerprint(&ty));
                     let Version {
                         major,
                         minor,
                         patch,
                         ..
-                    } = parse_attribute(ident.span(), &field.attrs)?;
+                    } = attributes.since;
                     *highest_version =
                         match (highest_version.take(), Version::new(major, minor, patch)) {
                             (None, v) => Some(v),
                             (Some(v1), v2) => Some(if v1 > v2 { v1 } else { v2 }),
                         };
+                    let present = attributes.present();
                     field_names.push(ident.clone());
-                    field_serialize.push(quote! {
-                        #ident.serialize(writer)?;
-                    });
+                    field_serialize.push(serialize);
+                    field_max_sizes.push(attributes.max_size(&ty));
                     field_deserialize.push(quote! {
-                        let #ident: #ty = if version < binver::Version::new(#major, #minor, #patch) {
-                            Default::default()
+                        let #ident: #ty = if #present {
+                            #deserialize
                         } else {
-                            binver::Serializable::deserialize(reader)?
+                            Default::default()
                         };
                     });
                 }
@@ -267,19 +334,21 @@ impl EnumVariantSerDeResult {
                 Ok(Self {
                     ser: quote! {
                         Self:: #ident { #(#field_names, )* } => {
+                            #check_feature
                             #index.serialize(writer)?;
                             #(#field_serialize)*
                             Ok(())
                         },
                     },
                     de: quote! {
-                        #index if version >= binver::Version::new(#major, #minor, #patch) => {
+                        #index if #present => {
                             #(#field_deserialize)*
                             Self::#ident {
                                 #(#field_names, )*
                             }
                         }
                     },
+                    max_size: quote! { binver::max_size_sum(&[#(#field_max_sizes),*]) },
                 })
             }
             Fields::Unnamed(fields) => {
@@ -288,30 +357,36 @@ impl EnumVariantSerDeResult {
                 let mut field_names = Vec::new();
                 let mut field_serialize = Vec::new();
                 let mut field_deserialize = Vec::new();
+                let mut field_max_sizes = Vec::new();
 
                 for (idx, field) in fields.unnamed.into_iter().enumerate() {
                     let ident = Ident::new(&format!("p_{}", idx), Span::call_site().into());
                     let ty = field.ty;
+                    let attributes = FieldAttributes::parse(ident.span(), &field.attrs)?;
+                    let serialize = attributes.serialize(quote! { #ident });
+                    let deserialize = attributes.deserialize(&ty);
+                    *schema += &attributes.schema(&ident.to_string());
+                    fingerprints.push(attributes.fingerprint(&ty));
                     let Version {
                         major,
                         minor,
                         patch,
                         ..
-                    } = parse_attribute(ident.span(), &field.attrs)?;
+                    } = attributes.since;
                     *highest_version =
                         match (highest_version.take(), Version::new(major, minor, patch)) {
                             (None, v) => Some(v),
                             (Some(v1), v2) => Some(if v1 > v2 { v1 } else { v2 }),
                         };
+                    let present = attributes.present();
                     field_names.push(ident.clone());
-                    field_serialize.push(quote! {
-                        #ident.serialize(writer)?;
-                    });
+                    field_serialize.push(serialize);
+                    field_max_sizes.push(attributes.max_size(&ty));
                     field_deserialize.push(quote! {
-                        let #ident: #ty = if version < binver::Version::new(#major, #minor, #patch) {
-                            Default::default()
+                        let #ident: #ty = if #present {
+                            #deserialize
                         } else {
-                            binver::Serializable::deserialize(reader)?
+                            Default::default()
                         };
                     });
                 }
@@ -319,19 +394,21 @@ impl EnumVariantSerDeResult {
                 Ok(Self {
                     ser: quote! {
                         Self:: #ident( #(#field_names, )* ) => {
+                            #check_feature
                             #index.serialize(writer)?;
                             #(#field_serialize)*
                             Ok(())
                         },
                     },
                     de: quote! {
-                        #index if version >= binver::Version::new(#major, #minor, #patch) => {
+                        #index if #present => {
                             #(#field_deserialize)*
                             Self::#ident (
                                 #(#field_names, )*
                             )
                         }
                     },
+                    max_size: quote! { binver::max_size_sum(&[#(#field_max_sizes),*]) },
                 })
             }
             Fields::Unit => {
@@ -341,44 +418,294 @@ impl EnumVariantSerDeResult {
                 if variant.discriminant.is_some() {
                     Ok(Self {
                         ser: quote! {
-                            Self:: #ident => (Self::#ident as u16).serialize(writer),
+                            Self:: #ident => {
+                                #check_feature
+                                (Self::#ident as u16).serialize(writer)
+                            }
                         },
                         de: quote! {
-                            Self::#ident if version >= binver::Version::new(#major, #minor, #patch) => Self:: #ident,
+                            Self::#ident if #present => Self:: #ident,
                         },
+                        max_size: quote! { Some(0) },
                     })
                 } else {
                     Ok(Self {
                         ser: quote! {
-                            Self:: #ident => #index .serialize(writer),
+                            Self:: #ident => {
+                                #check_feature
+                                #index.serialize(writer)
+                            }
                         },
                         de: (quote! {
-                            #index if version >= binver::Version::new(#major, #minor, #patch) => Self:: #ident,
+                            #index if #present => Self:: #ident,
                         }),
+                        max_size: quote! { Some(0) },
                     })
                 }
             }
-        }
+        };
+        *schema += ");";
+        result
     }
 }
 
-fn parse_attribute(span: proc_macro2::Span, attr: &[Attribute]) -> Result<Version, Error> {
-    if attr.len() != 1 {
-        return Err(Error::new(
-            span,
-            "Expected exactly 1 attribute: `since(version)`",
-        ));
+/// The `#[binver(..)]` attributes of a struct or enum
+struct ContainerAttributes {
+    /// `#[binver(domain = "..")]`
+    domain: Option<String>,
+}
+
+impl ContainerAttributes {
+    fn parse(attrs: &[Attribute]) -> Result<Self, Error> {
+        let mut domain = None;
+        for attr in attrs {
+            if !attr.path.is_ident("binver") {
+                continue;
+            }
+            for option in parse_binver_options(attr)? {
+                match option {
+                    NestedMeta::Meta(Meta::NameValue(value)) if value.path.is_ident("domain") => {
+                        match value.lit {
+                            Lit::Str(s) if !s.value().is_empty() => domain = Some(s.value()),
+                            lit => {
+                                return Err(Error::new(
+                                    lit.span(),
+                                    "Expected a domain name: `domain = \"name\"`",
+                                ))
+                            }
+                        }
+                    }
+                    option => {
+                        return Err(Error::new(option.span(), "Unknown binver option"));
+                    }
+                }
+            }
+        }
+        Ok(Self { domain })
+    }
+
+    /// The part of the type's schema that describes the container attributes
+    fn schema(&self) -> String {
+        match &self.domain {
+            Some(domain) => format!("@{}", domain),
+            None => String::new(),
+        }
     }
-    let attr = attr.first().unwrap();
-    match attr.path.segments.first() {
-        Some(i) if i.ident.to_string() == String::from("since") => {}
-        _ => {
+
+    /// Get the version that the `#[since(..)]` attributes are compared against
+    fn read_version(&self) -> proc_macro2::TokenStream {
+        match &self.domain {
+            Some(domain) => quote! { reader.domain_version(#domain) },
+            None => quote! { reader.version() },
+        }
+    }
+}
+
+/// The `#[since(..)]` and `#[binver(..)]` attributes of a field or variant
+struct FieldAttributes {
+    /// `#[since(..)]`, or `0.0.0` for fields and variants that are only gated by a feature
+    since: Version,
+    /// `false` if there is no `#[since(..)]` attribute
+    has_since: bool,
+    /// `#[binver(compress)]`
+    compress: bool,
+    /// `#[binver(bytes)]`
+    bytes: bool,
+    /// `#[binver(varint)]`
+    varint: bool,
+    /// `#[binver(feature = "..")]`
+    feature: Option<String>,
+}
+
+impl FieldAttributes {
+    fn parse(span: proc_macro2::Span, attrs: &[Attribute]) -> Result<Self, Error> {
+        let mut since = None;
+        let mut compress = false;
+        let mut bytes = false;
+        let mut varint = false;
+        let mut feature = None;
+        for attr in attrs {
+            if attr.path.is_ident("since") {
+                if since.is_some() {
+                    return Err(Error::new(
+                        attr.span(),
+                        "Expected exactly 1 attribute: `since(version)`",
+                    ));
+                }
+                since = Some(parse_since(attr)?);
+            } else if attr.path.is_ident("binver") {
+                for option in parse_binver_options(attr)? {
+                    match option {
+                        NestedMeta::Meta(Meta::Path(path)) if path.is_ident("compress") => {
+                            compress = true;
+                        }
+                        NestedMeta::Meta(Meta::Path(path)) if path.is_ident("bytes") => {
+                            bytes = true;
+                        }
+                        NestedMeta::Meta(Meta::Path(path)) if path.is_ident("varint") => {
+                            varint = true;
+                        }
+                        NestedMeta::Meta(Meta::NameValue(value))
+                            if value.path.is_ident("feature") =>
+                        {
+                            match value.lit {
+                                Lit::Str(s) if !s.value().is_empty() => feature = Some(s.value()),
+                                lit => {
+                                    return Err(Error::new(
+                                        lit.span(),
+                                        "Expected a feature name: `feature = \"name\"`",
+                                    ))
+                                }
+                            }
+                        }
+                        option => {
+                            return Err(Error::new(option.span(), "Unknown binver option"));
+                        }
+                    }
+                }
+            }
+        }
+        if [compress, bytes, varint].iter().filter(|set| **set).count() > 1 {
             return Err(Error::new(
                 span,
-                "Expected exactly 1 attribute: `since(version)`",
+                "Only one of `compress`, `bytes` and `varint` can be used on the same field",
             ));
         }
+        match (since, feature) {
+            (Some(since), feature) => Ok(Self {
+                since,
+                has_since: true,
+                compress,
+                bytes,
+                varint,
+                feature,
+            }),
+            // Fields that are gated by a feature don't need a version
+            (None, Some(feature)) => Ok(Self {
+                since: Version::new(0, 0, 0),
+                has_since: false,
+                compress,
+                bytes,
+                varint,
+                feature: Some(feature),
+            }),
+            (None, None) => Err(Error::new(
+                span,
+                "Expected exactly 1 attribute: `since(version)` or `binver(feature = \"name\")`",
+            )),
+        }
     }
+
+    /// Get the condition for this field or variant to be present in the data being read
+    fn present(&self) -> proc_macro2::TokenStream {
+        let Version {
+            major,
+            minor,
+            patch,
+            ..
+        } = self.since;
+        let since = quote! { version >= binver::Version::new(#major, #minor, #patch) };
+        match &self.feature {
+            Some(feature) => quote! { #since && reader.has_feature(#feature) },
+            None => since,
+        }
+    }
+
+    /// Return an error if the variant is written without its feature enabled
+    fn check_feature(&self) -> proc_macro2::TokenStream {
+        match &self.feature {
+            Some(feature) => quote! {
+                if !writer.has_feature(#feature) {
+                    return Err(binver::WriteError::FeatureDisabled(#feature));
+                }
+            },
+            None => quote! {},
+        }
+    }
+
+    /// The part of the type's schema that describes this field. The type of the field is added with `fingerprint`.
+    fn schema(&self, name: &str) -> String {
+        let mut schema = format!("{}@{}", name, self.since);
+        if self.compress {
+            schema += "+compress";
+        }
+        if self.bytes {
+            schema += "+bytes";
+        }
+        if self.varint {
+            schema += "+varint";
+        }
+        if let Some(feature) = &self.feature {
+            schema += &format!("+feature={}", feature);
+        }
+        schema + ","
+    }
+
+    /// The fingerprint of the type of this field. Byte fields are encoded the same as a `Vec<u8>`, whatever their type.
+    fn fingerprint(&self, ty: &Type) -> proc_macro2::TokenStream {
+        if self.bytes {
+            quote! { <&[u8] as binver::Serializable>::FINGERPRINT }
+        } else {
+            quote! { <#ty as binver::Serializable>::FINGERPRINT }
+        }
+    }
+
+    /// Serialize `value`, which is a reference to the field. Fields that are gated by a feature are only written if the writer has the feature enabled.
+    fn serialize(&self, value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let serialize = if self.compress {
+            quote! { binver::compression::serialize_field(#value, writer)?; }
+        } else if self.bytes {
+            quote! { binver::serialize_bytes_field(#value, writer)?; }
+        } else if self.varint {
+            quote! { binver::serialize_varint_field(#value, writer)?; }
+        } else {
+            quote! { binver::Serializable::serialize(#value, writer)?; }
+        };
+        match &self.feature {
+            Some(feature) => quote! {
+                if writer.has_feature(#feature) {
+                    #serialize
+                }
+            },
+            None => serialize,
+        }
+    }
+
+    /// The maximum serialized size of this field. Compressed fields and byte fields have no maximum size.
+    fn max_size(&self, ty: &Type) -> proc_macro2::TokenStream {
+        if self.compress || self.bytes {
+            quote! { None }
+        } else if self.varint {
+            quote! { binver::max_size_varint(<#ty as binver::Serializable>::MAX_SERIALIZED_SIZE) }
+        } else {
+            quote! { <#ty as binver::Serializable>::MAX_SERIALIZED_SIZE }
+        }
+    }
+
+    fn deserialize(&self, ty: &Type) -> proc_macro2::TokenStream {
+        if self.compress {
+            quote! { binver::compression::deserialize_field::<#ty, _>(reader)? }
+        } else if self.bytes {
+            quote! { binver::deserialize_bytes_field::<#ty, _>(reader)? }
+        } else if self.varint {
+            quote! { binver::deserialize_varint_field::<#ty, _>(reader)? }
+        } else {
+            quote! { <#ty as binver::Serializable>::deserialize(reader)? }
+        }
+    }
+}
+
+fn parse_binver_options(attr: &Attribute) -> Result<Vec<NestedMeta>, Error> {
+    match attr.parse_meta()? {
+        Meta::List(list) => Ok(list.nested.into_iter().collect()),
+        meta => Err(Error::new(
+            meta.span(),
+            "Expected a list of options: `binver(option, ..)`",
+        )),
+    }
+}
+
+fn parse_since(attr: &Attribute) -> Result<Version, Error> {
     let content = attr.tokens.to_string();
     let version_str = content.trim_start_matches('(').trim_end_matches(')');
     let version_string = version_str.replace(" ", ""); // sometimes in CI, spaces are inserted. We want to ignore this
@@ -0,0 +1,85 @@
+//! (De)serialize `Serializable` objects over `futures_io::AsyncRead` and `futures_io::AsyncWrite` streams.
+//!
+//! The `Reader` and `Writer` traits are synchronous, so these functions encode into and decode from an internal buffer,
+//! and only touch the stream with non-blocking reads and writes.
+//!
+//! Tokio streams can be used through `tokio_util::compat`.
+
+use crate::{
+    ReadConfig, ReadError, ReadOptions, ReadResult, Serializable, SerializableOwned, WriteConfig,
+    WriteError, WriteResult,
+};
+use alloc::vec::Vec;
+use core::{future::poll_fn, pin::Pin};
+use futures_io::{AsyncRead, AsyncWrite};
+use std::io::ErrorKind;
+
+/// Serialize the given `Serializable` object into the given async writer.
+///
+/// The object is first serialized into an internal buffer, which is then written to the writer and flushed.
+pub async fn to_async_writer<'a, W, T>(writer: &mut W, t: &T) -> WriteResult
+where
+    W: AsyncWrite + Unpin + ?Sized,
+    T: Serializable<'a>,
+{
+    to_async_writer_with_config(writer, t, WriteConfig::default()).await
+}
+
+/// Serialize the given `Serializable` object into the given async writer with the given `WriteConfig`. See `WriteConfig` for information on the options.
+pub async fn to_async_writer_with_config<'a, W, T>(
+    writer: &mut W,
+    t: &T,
+    config: WriteConfig<'_>,
+) -> WriteResult
+where
+    W: AsyncWrite + Unpin + ?Sized,
+    T: Serializable<'a>,
+{
+    let buffer = crate::try_to_vec_with_config(t, config)?;
+    let mut written = 0;
+    while written < buffer.len() {
+        let result = poll_fn(|cx| Pin::new(&mut *writer).poll_write(cx, &buffer[written..])).await;
+        match result {
+            Ok(0) => return Err(WriteError::EndOfOutput),
+            Ok(n) => written += n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(WriteError::Io(e)),
+        }
+    }
+    poll_fn(|cx| Pin::new(&mut *writer).poll_flush(cx))
+        .await
+        .map_err(WriteError::Io)
+}
+
+/// Deserialize an object from the given async reader.
+///
+/// The reader is read until it reaches the end of the stream. To send multiple objects over a single stream, use a framing protocol.
+pub async fn from_async_reader<T, R>(reader: &mut R) -> ReadResult<T>
+where
+    T: SerializableOwned,
+    R: AsyncRead + Unpin + ?Sized,
+{
+    from_async_reader_with_config(reader, ReadConfig::default()).await
+}
+
+/// Deserialize an object from the given async reader with the given `ReadConfig`. See `ReadConfig` for information on the options.
+pub async fn from_async_reader_with_config<T, R>(
+    reader: &mut R,
+    config: impl Into<ReadOptions>,
+) -> ReadResult<T>
+where
+    T: SerializableOwned,
+    R: AsyncRead + Unpin + ?Sized,
+{
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        match poll_fn(|cx| Pin::new(&mut *reader).poll_read(cx, &mut chunk)).await {
+            Ok(0) => break,
+            Ok(n) => buffer.extend_from_slice(&chunk[..n]),
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(ReadError::Io(e)),
+        }
+    }
+    crate::deserialize_slice_with_config(&buffer, config)
+}
@@ -0,0 +1,175 @@
+//! Documents made of a sequence of chunks, similar to RIFF or PNG files.
+//!
+//! Every chunk has a 4-byte type id and a big-endian `u32` length, followed by a version header and the serialized object.
+//! Readers can skip over chunks with an id they don't recognize, so different parts of an application can add their own chunks to a document.
+//!
+//! ```rust
+//! # use binver::{chunk::{self, ChunkReader}, Serializable};
+//! # #[cfg(feature = "std")]
+//! # {
+//! #[derive(Serializable, PartialEq, Debug)]
+//! pub struct World {
+//!     #[since(0.0.1)]
+//!     pub seed: u64,
+//! }
+//!
+//! let mut document = Vec::new();
+//! chunk::write_chunk(&mut document, *b"WRLD", &World { seed: 42 }).unwrap();
+//! chunk::write_chunk(&mut document, *b"PLGN", &String::from("plugin data")).unwrap();
+//!
+//! let world: World = ChunkReader::new(&document)
+//!     .find_chunk(*b"WRLD")
+//!     .unwrap()
+//!     .unwrap()
+//!     .decode()
+//!     .unwrap();
+//! assert_eq!(world, World { seed: 42 });
+//! # }
+//! ```
+
+use crate::{ReadConfig, ReadError, ReadOptions, ReadResult, Serializable, Version};
+#[cfg(feature = "std")]
+use crate::{WriteConfig, WriteError, WriteResult, Writer};
+
+/// The type id of a chunk.
+pub type ChunkId = [u8; 4];
+
+const CHUNK_HEADER_SIZE: usize = 8;
+
+#[cfg(feature = "std")]
+/// Serialize the given `Serializable` object as a chunk with the given id, and write it to the writer.
+pub fn write_chunk<'a, T: Serializable<'a>, W: Writer + ?Sized>(
+    writer: &mut W,
+    id: ChunkId,
+    t: &T,
+) -> WriteResult {
+    write_chunk_with_config(writer, id, t, WriteConfig::default())
+}
+
+#[cfg(feature = "std")]
+/// Serialize the given `Serializable` object as a chunk with the given id and `WriteConfig`, and write it to the writer.
+///
+/// Chunks are recognized by their id, so `WriteConfig::magic` is not written.
+/// Returns `WriteError::ChunkTooLarge` if the serialized object is larger than `u32::MAX` bytes.
+pub fn write_chunk_with_config<'a, T: Serializable<'a>, W: Writer + ?Sized>(
+    writer: &mut W,
+    id: ChunkId,
+    t: &T,
+    config: WriteConfig,
+) -> WriteResult {
+    let config = WriteConfig {
+        magic: None,
+        ..config
+    };
+    let body = crate::try_to_vec_with_config(t, config)?;
+    if body.len() > u32::MAX as usize {
+        return Err(WriteError::ChunkTooLarge(body.len()));
+    }
+    writer.write(&id)?;
+    // The chunk header is always big-endian, regardless of the `WriteConfig`
+    writer.write(&(body.len() as u32).to_be_bytes())?;
+    writer.write(&body)
+}
+
+/// A single chunk in a document.
+#[derive(Debug, Clone)]
+pub struct Chunk<'a> {
+    /// The type id of this chunk.
+    pub id: ChunkId,
+    /// The version this chunk was written with.
+    pub version: Version,
+    /// The binver wire format revision this chunk was written with.
+    pub revision: u8,
+    /// The serialized object, without the version header.
+    pub body: &'a [u8],
+    /// The version header and the serialized object.
+    data: &'a [u8],
+}
+
+impl<'a> Chunk<'a> {
+    /// Deserialize the object in this chunk.
+    pub fn decode<T: Serializable<'a>>(&self) -> ReadResult<T> {
+        self.decode_with_config(ReadConfig::default())
+    }
+
+    /// Deserialize the object in this chunk with the given `ReadConfig`. See `ReadConfig` for information on the options.
+    ///
+    /// Chunks never contain magic bytes, so `ReadOptions::with_magic` is ignored.
+    pub fn decode_with_config<T: Serializable<'a>>(
+        &self,
+        config: impl Into<ReadOptions>,
+    ) -> ReadResult<T> {
+        let config = ReadOptions {
+            magic: None,
+            ..config.into()
+        };
+        // The header is read again, so the domains and features in it are used
+        crate::deserialize_slice_with_config(self.data, config)
+    }
+}
+
+/// Iterates over the chunks in a document.
+///
+/// After a chunk could not be read, the iterator stops.
+pub struct ChunkReader<'a> {
+    slice: &'a [u8],
+}
+
+impl<'a> ChunkReader<'a> {
+    /// Create a reader for the chunks in the given slice.
+    pub fn new(slice: &'a [u8]) -> Self {
+        Self { slice }
+    }
+
+    /// Find the first chunk with the given id, skipping all other chunks.
+    pub fn find_chunk(&mut self, id: ChunkId) -> ReadResult<Option<Chunk<'a>>> {
+        for chunk in self {
+            let chunk = chunk?;
+            if chunk.id == id {
+                return Ok(Some(chunk));
+            }
+        }
+        Ok(None)
+    }
+
+    fn read_chunk(&mut self) -> ReadResult<Chunk<'a>> {
+        if self.slice.len() < CHUNK_HEADER_SIZE {
+            return Err(ReadError::EndOfInput);
+        }
+        let mut id = [0u8; 4];
+        id.copy_from_slice(&self.slice[..4]);
+        let mut len = [0u8; 4];
+        len.copy_from_slice(&self.slice[4..CHUNK_HEADER_SIZE]);
+        let len = u32::from_be_bytes(len) as usize;
+
+        let rest = &self.slice[CHUNK_HEADER_SIZE..];
+        if rest.len() < len {
+            return Err(ReadError::EndOfInput);
+        }
+        let (data, rest) = rest.split_at(len);
+        let (header, body) = crate::helpers::split_version_header(data)?;
+        self.slice = rest;
+        Ok(Chunk {
+            id,
+            version: header.version,
+            revision: header.revision,
+            body,
+            data,
+        })
+    }
+}
+
+impl<'a> Iterator for ChunkReader<'a> {
+    type Item = ReadResult<Chunk<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.slice.is_empty() {
+            return None;
+        }
+        let result = self.read_chunk();
+        if result.is_err() {
+            self.slice = &[];
+        }
+        Some(result)
+    }
+}
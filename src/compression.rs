@@ -0,0 +1,235 @@
+//! Compression of whole documents or of single fields.
+//!
+//! A compressed document starts with the regular version header with a flag that marks the document as compressed, followed by a `Codec` byte,
+//! the uncompressed length as a `u32`, the compressed length as a `u32` and the compressed bytes.
+//! Because the codec is stored in the document, `deserialize_slice` and `deserialize_slice_compressed` can read documents written with any codec.
+//! The decompressed data does not outlive the call, so types that borrow from the slice, e.g. a `&str`, can not be read from a compressed document.
+//!
+//! Single fields can be compressed by adding `#[binver(compress)]` to them. Compressed fields must be owned, e.g. a `Vec<T>` or a `String`.
+//!
+//! ```rust
+//! # use binver::{compression::{self, Codec}, Serializable};
+//! #[derive(Serializable, PartialEq, Debug)]
+//! pub struct World {
+//!     #[since(0.0.1)]
+//!     pub name: String,
+//!     #[since(0.0.1)]
+//!     #[binver(compress)]
+//!     pub tiles: Vec<u32>,
+//! }
+//!
+//! let world = World {
+//!     name: String::from("overworld"),
+//!     tiles: vec![1; 4096],
+//! };
+//! let serialized = compression::to_vec_compressed(&world, Codec::Deflate);
+//! assert!(serialized.len() < 100);
+//!
+//! let deserialized: World = compression::deserialize_slice_compressed(&serialized).unwrap();
+//! assert_eq!(world, deserialized);
+//! ```
+
+use crate::{
+    helpers::{BufferWriter, ConfiguredWriter},
+    ByteOrder, IntegerEncoding, ReadConfig, ReadError, ReadOptions, ReadResult, Reader,
+    Serializable, SerializableOwned, Version, WriteConfig, WriteResult, Writer,
+};
+use alloc::vec::Vec;
+
+/// The compression algorithm that is used for a document or field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Codec {
+    /// The data is stored without compression.
+    None = 0,
+    /// The data is compressed with raw DEFLATE.
+    Deflate = 1,
+}
+
+impl Codec {
+    fn from_u8(codec: u8) -> ReadResult<Self> {
+        match codec {
+            0 => Ok(Codec::None),
+            1 => Ok(Codec::Deflate),
+            x => Err(ReadError::UnknownCodec(x)),
+        }
+    }
+
+    fn compress(self, bytes: &[u8]) -> Vec<u8> {
+        match self {
+            Codec::None => bytes.to_vec(),
+            Codec::Deflate => miniz_oxide::deflate::compress_to_vec(bytes, 6),
+        }
+    }
+
+    fn decompress(self, bytes: &[u8], len: usize) -> ReadResult<Vec<u8>> {
+        let result = match self {
+            Codec::None => bytes.to_vec(),
+            Codec::Deflate => miniz_oxide::inflate::decompress_to_vec_with_limit(bytes, len)
+                .map_err(|_| ReadError::DecompressionFailed)?,
+        };
+        if result.len() != len {
+            return Err(ReadError::DecompressionFailed);
+        }
+        Ok(result)
+    }
+}
+
+/// Write the codec, the uncompressed length, the compressed length and the compressed bytes.
+fn write_compressed<W: Writer + ?Sized>(writer: &mut W, codec: Codec, bytes: &[u8]) -> WriteResult {
+    (codec as u8).serialize(writer)?;
+    (bytes.len() as u32).serialize(writer)?;
+    let compressed = codec.compress(bytes);
+    (compressed.len() as u32).serialize(writer)?;
+    writer.write(&compressed)
+}
+
+/// Read the data written by `write_compressed`, and return the uncompressed bytes.
+fn read_compressed<'a, R: Reader<'a> + ?Sized>(reader: &mut R) -> ReadResult<Vec<u8>> {
+    let codec = Codec::from_u8(u8::deserialize(reader)?)?;
+    let len = u32::deserialize(reader)? as usize;
+    let compressed_len = u32::deserialize(reader)? as usize;
+    let compressed = crate::implementations::read_chunked(reader, compressed_len)?;
+    codec.decompress(&compressed, len)
+}
+
+/// Serialize the given `Serializable` object to a vec, compressing everything after the version header with the given codec.
+///
+/// This panics if the object can not be written, use `to_vec_compressed_with_config` to handle this error.
+pub fn to_vec_compressed<'a, T: Serializable<'a>>(t: &T, codec: Codec) -> Vec<u8> {
+    to_vec_compressed_with_config(t, codec, WriteConfig::default()).unwrap()
+}
+
+/// Serialize the given `Serializable` object to a vec with the given `WriteConfig`, compressing everything after the header with the given codec.
+pub fn to_vec_compressed_with_config<'a, T: Serializable<'a>>(
+    t: &T,
+    codec: Codec,
+    config: WriteConfig,
+) -> WriteResult<Vec<u8>> {
+    let mut document = Vec::new();
+    let mut writer = ConfiguredWriter::new(&mut document, &config)?;
+    crate::helpers::write_header(&mut writer, &config, T::FINGERPRINT, true)?;
+    let mut payload = BufferWriter::new(&writer);
+    t.serialize(&mut payload)?;
+    let payload = payload.buffer;
+    write_compressed(&mut writer, codec, &payload)?;
+    Ok(document)
+}
+
+/// Deserialize an object from a slice that was serialized with `to_vec_compressed`.
+pub fn deserialize_slice_compressed<T: SerializableOwned>(slice: &[u8]) -> ReadResult<T> {
+    deserialize_slice_compressed_with_config(slice, ReadConfig::default())
+}
+
+/// Deserialize an object from a compressed slice with the given `ReadConfig`. See `ReadConfig` for information on the options.
+///
+/// Returns `ReadError::NotCompressed` if the header does not mark the document as compressed.
+pub fn deserialize_slice_compressed_with_config<T: SerializableOwned>(
+    slice: &[u8],
+    config: impl Into<ReadOptions>,
+) -> ReadResult<T> {
+    let config = config.into();
+    let (header, tables, slice) = crate::helpers::read_header(slice, &config, T::FINGERPRINT)?;
+    if !tables.is_compressed() {
+        return Err(ReadError::NotCompressed);
+    }
+    crate::helpers::deserialize_slice_with_header(slice, header, tables, config)
+}
+
+/// Serialize a field that is marked with `#[binver(compress)]`.
+#[doc(hidden)]
+pub fn serialize_field<'a, T: Serializable<'a>, W: Writer + ?Sized>(
+    t: &T,
+    writer: &mut W,
+) -> WriteResult {
+    let mut payload = BufferWriter::new(&*writer);
+    t.serialize(&mut payload)?;
+    let payload = payload.buffer;
+    write_compressed(writer, Codec::Deflate, &payload)
+}
+
+/// Deserialize a document of which the header marks it as compressed, from a reader that is positioned after the header.
+///
+/// The payload is read from a buffer, so types that borrow from the reader return `ReadError::ReaderNotPersistent`.
+pub(crate) fn deserialize_document<'a, T: Serializable<'a>, R: Reader<'a> + ?Sized>(
+    reader: &mut R,
+    config: &ReadOptions,
+) -> ReadResult<T> {
+    deserialize_payload(reader, config.error_on_trailing_bytes)
+}
+
+/// Deserialize a field that is marked with `#[binver(compress)]`.
+#[doc(hidden)]
+pub fn deserialize_field<'a, T: SerializableOwned, R: Reader<'a> + ?Sized>(
+    reader: &mut R,
+) -> ReadResult<T> {
+    deserialize_payload(reader, true)
+}
+
+/// Read and decompress the data written by `write_compressed`, and deserialize it the same way as the document it is in.
+fn deserialize_payload<'a, T: Serializable<'a>, R: Reader<'a> + ?Sized>(
+    reader: &mut R,
+    error_on_trailing_bytes: bool,
+) -> ReadResult<T> {
+    let payload = read_compressed(reader)?;
+    let mut payload_reader = PayloadReader {
+        payload: &payload,
+        index: 0,
+        outer: reader,
+    };
+    let result = T::deserialize(&mut payload_reader)?;
+    match payload.len() - payload_reader.index {
+        remaining if error_on_trailing_bytes && remaining != 0 => {
+            Err(ReadError::TrailingBytes(remaining))
+        }
+        _ => Ok(result),
+    }
+}
+
+/// A reader over decompressed bytes, that reads them the same way as the document they are in.
+///
+/// The decompressed bytes do not outlive the reader, so it can not lend out slices.
+struct PayloadReader<'p, 'r, R: ?Sized> {
+    payload: &'p [u8],
+    index: usize,
+    outer: &'r R,
+}
+
+impl<'p, 'r, 'a, R: Reader<'a> + ?Sized> Reader<'a> for PayloadReader<'p, 'r, R> {
+    fn version(&self) -> Version {
+        self.outer.version()
+    }
+
+    fn wire_revision(&self) -> u8 {
+        self.outer.wire_revision()
+    }
+
+    fn domain_version(&self, domain: &str) -> Version {
+        self.outer.domain_version(domain)
+    }
+
+    fn has_feature(&self, feature: &str) -> bool {
+        self.outer.has_feature(feature)
+    }
+
+    fn integer_encoding(&self) -> IntegerEncoding {
+        self.outer.integer_encoding()
+    }
+
+    fn byte_order(&self) -> ByteOrder {
+        self.outer.byte_order()
+    }
+
+    fn read(&mut self, bytes: &mut [u8]) -> ReadResult {
+        let slice = self.payload[self.index..]
+            .get(..bytes.len())
+            .ok_or(ReadError::EndOfInput)?;
+        bytes.copy_from_slice(slice);
+        self.index += bytes.len();
+        Ok(())
+    }
+
+    fn read_slice(&mut self, _len: usize) -> ReadResult<&'a [u8]> {
+        Err(ReadError::ReaderNotPersistent)
+    }
+}
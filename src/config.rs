@@ -1,6 +1,149 @@
+use crate::Version;
+
 /// Configuration passed to a read function
 #[derive(Default, Clone)]
 pub struct ReadConfig {
     /// Set this to `true` to make the serialize functions return `ReadError::TrailingBytes` if the reader is not empty.
     pub error_on_trailing_bytes: bool,
 }
+
+impl ReadConfig {
+    /// Require the data to start with the given magic bytes. See `ReadOptions::with_magic`.
+    pub fn with_magic(self, magic: &'static [u8]) -> ReadOptions {
+        ReadOptions::from(self).with_magic(magic)
+    }
+
+    /// Read and check the type fingerprint in the header. See `ReadOptions::with_type_fingerprint`.
+    pub fn with_type_fingerprint(self) -> ReadOptions {
+        ReadOptions::from(self).with_type_fingerprint()
+    }
+}
+
+/// A `ReadConfig` together with the options that must match the `WriteConfig` the data was written with.
+///
+/// Every function that takes a `ReadOptions` also accepts a `ReadConfig`.
+///
+/// ```rust
+/// # use binver::{ReadConfig, WriteConfig};
+/// let write_config = WriteConfig {
+///     magic: Some(b"SAVE"),
+///     type_fingerprint: true,
+///     ..WriteConfig::default()
+/// };
+/// let mut slice = [0u8; 32];
+/// let length = binver::write_to_slice_with_config(&mut slice, &5u32, write_config).unwrap();
+///
+/// let read_options = ReadConfig::default()
+///     .with_magic(b"SAVE")
+///     .with_type_fingerprint();
+/// let deserialized: u32 = binver::deserialize_slice_with_config(&slice[..length], read_options).unwrap();
+/// assert_eq!(deserialized, 5);
+/// ```
+#[derive(Default, Clone)]
+pub struct ReadOptions {
+    pub(crate) error_on_trailing_bytes: bool,
+    pub(crate) magic: Option<&'static [u8]>,
+    pub(crate) type_fingerprint: bool,
+}
+
+impl ReadOptions {
+    /// Require the data to start with the given magic bytes, otherwise `ReadError::InvalidMagic` is returned.
+    ///
+    /// This should be the same as the `WriteConfig::magic` that the data was written with.
+    pub fn with_magic(mut self, magic: &'static [u8]) -> Self {
+        self.magic = Some(magic);
+        self
+    }
+
+    /// Read and check the type fingerprint in the header. Use this if the data was written with `WriteConfig::type_fingerprint`.
+    ///
+    /// If the fingerprint does not match the `Serializable::FINGERPRINT` of the type being read, `ReadError::TypeMismatch` is returned.
+    pub fn with_type_fingerprint(mut self) -> Self {
+        self.type_fingerprint = true;
+        self
+    }
+}
+
+impl From<ReadConfig> for ReadOptions {
+    fn from(config: ReadConfig) -> Self {
+        Self {
+            error_on_trailing_bytes: config.error_on_trailing_bytes,
+            ..Self::default()
+        }
+    }
+}
+
+/// Configuration passed to a write function
+#[derive(Default, Clone)]
+pub struct WriteConfig<'c> {
+    /// If set, these bytes are written before the version header, so the data can be identified as a specific file format.
+    ///
+    /// Use `binver::MAGIC` to make the data recognizable by `binver::sniff`.
+    pub magic: Option<&'static [u8]>,
+
+    /// The schema version that is written in the header, and that `#[since(..)]` attributes are compared against when reading the data.
+    /// If not set, `binver::VERSION` is used.
+    ///
+    /// All fields are always written, so this must not be lower than any `#[since(..)]` attribute of the types being written.
+    pub schema_version: Option<Version>,
+
+    /// The versions of the domains that are used by the types being written, e.g. `&[("corelib", corelib::VERSION.clone())]`.
+    ///
+    /// Types with a `#[binver(domain = "..")]` attribute compare their `#[since(..)]` attributes against the version of their domain,
+    /// instead of `schema_version`. Domains that are not in this table use `schema_version`.
+    pub domains: &'c [(&'c str, Version)],
+
+    /// The features that are enabled, e.g. `&["guilds"]`. These are written in the header.
+    ///
+    /// Fields with a `#[binver(feature = "..")]` attribute are only written if their feature is enabled.
+    /// Writing an enum variant of which the feature is not enabled returns `WriteError::FeatureDisabled`.
+    /// Enum variants are stored by their position, so a variant that is only gated by a feature must be added at the bottom of the enum, like a new version.
+    pub features: &'c [&'c str],
+
+    /// How integers and lengths are encoded. See `IntegerEncoding` for more information.
+    ///
+    /// The encoding is recorded in the header, so the data can be read without configuring it.
+    /// Documents written with `IntegerEncoding::Varint` require wire revision 2 or later.
+    pub integer_encoding: IntegerEncoding,
+
+    /// The byte order of integers that are written with their full width, including the lengths of strings and vecs.
+    ///
+    /// The byte order is recorded in the header, so the data can be read without configuring it.
+    /// Documents written with `ByteOrder::LittleEndian` require wire revision 2 or later.
+    pub byte_order: ByteOrder,
+
+    /// Set this to `true` to write all NaN floats as the same canonical quiet NaN, instead of keeping their sign and payload.
+    ///
+    /// This makes the output deterministic, e.g. when the data is hashed or compared.
+    pub canonical_nan: bool,
+
+    /// Write the data with an older revision of the binver wire format, so it can be read by older versions of binver. See `WIRE_REVISION`.
+    /// If not set, `WIRE_REVISION` is used.
+    pub wire_revision: Option<u8>,
+
+    /// Set this to `true` to write the `Serializable::FINGERPRINT` of the type after the version header.
+    /// The data must then be read with `ReadOptions::with_type_fingerprint`.
+    pub type_fingerprint: bool,
+}
+
+/// How integers wider than a byte are encoded, including the lengths of strings and vecs.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IntegerEncoding {
+    /// Integers are written with their full width. This is the default.
+    #[default]
+    Fixed,
+    /// Integers are written as LEB128 varints, so small values take less bytes. Signed integers are zig-zag encoded first.
+    ///
+    /// This can be enabled for a whole document with `WriteConfig::integer_encoding`, or for a single field with `#[binver(varint)]`.
+    Varint,
+}
+
+/// The byte order of integers that are written with their full width.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ByteOrder {
+    /// The most significant byte is written first. This is the default.
+    #[default]
+    BigEndian,
+    /// The least significant byte is written first.
+    LittleEndian,
+}
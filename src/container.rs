@@ -0,0 +1,273 @@
+//! Containers of many independently versioned entries, that can be loaded one at a time.
+//!
+//! A container starts with `CONTAINER_MAGIC`, followed by the entries. Every entry is a regular binver blob with its own version header.
+//! After the entries an index is written that maps every key to the offset and length of its entry.
+//! The container ends with the offset of the index as a big-endian `u64`, followed by `CONTAINER_MAGIC` again.
+//!
+//! ```rust
+//! # use binver::{container::{ContainerSlice, ContainerWriter}, Serializable};
+//! #[derive(Serializable, PartialEq, Debug)]
+//! pub struct Texture {
+//!     #[since(0.0.1)]
+//!     pub width: u32,
+//!     #[since(0.0.1)]
+//!     pub height: u32,
+//! }
+//!
+//! let mut writer = ContainerWriter::new(Vec::new()).unwrap();
+//! writer.insert("grass", &Texture { width: 16, height: 16 }).unwrap();
+//! writer.insert("sky", &Texture { width: 1024, height: 512 }).unwrap();
+//! let bundle = writer.finish().unwrap();
+//!
+//! let container = ContainerSlice::new(&bundle).unwrap();
+//! let sky: Texture = container.get("sky").unwrap().unwrap();
+//! assert_eq!(sky, Texture { width: 1024, height: 512 });
+//! ```
+
+use crate::{
+    helpers::VersionHeader, ReadConfig, ReadError, ReadOptions, ReadResult, Reader, Serializable,
+    SerializableOwned, Version, WriteConfig, WriteResult, Writer,
+};
+use alloc::{string::String, vec, vec::Vec};
+use std::{
+    collections::HashMap,
+    io::{Read, Seek, SeekFrom, Write},
+};
+
+/// The bytes at the start and the end of every container.
+pub const CONTAINER_MAGIC: [u8; 4] = *b"BVCT";
+
+const FOOTER_SIZE: usize = 8 + CONTAINER_MAGIC.len();
+
+struct IndexEntry {
+    key: String,
+    offset: u64,
+    len: u64,
+}
+
+impl<'a> Serializable<'a> for IndexEntry {
+    fn serialize<W: Writer + ?Sized>(&self, writer: &mut W) -> WriteResult {
+        self.key.serialize(writer)?;
+        self.offset.serialize(writer)?;
+        self.len.serialize(writer)
+    }
+    fn deserialize<R: Reader<'a> + ?Sized>(reader: &mut R) -> ReadResult<Self> {
+        Ok(Self {
+            key: String::deserialize(reader)?,
+            offset: u64::deserialize(reader)?,
+            len: u64::deserialize(reader)?,
+        })
+    }
+}
+
+/// Writes entries to a container. Call `finish` to write the index, without it the container can not be read.
+pub struct ContainerWriter<W> {
+    writer: W,
+    position: u64,
+    index: Vec<IndexEntry>,
+}
+
+impl<W: Write> ContainerWriter<W> {
+    /// Start a new container in the given writer.
+    pub fn new(mut writer: W) -> WriteResult<Self> {
+        writer.write_all(&CONTAINER_MAGIC)?;
+        Ok(Self {
+            writer,
+            position: CONTAINER_MAGIC.len() as u64,
+            index: Vec::new(),
+        })
+    }
+
+    /// Write a single entry to the container. If an entry with the same key already exists, the new entry replaces it when reading.
+    pub fn insert<'a, T: Serializable<'a>>(&mut self, key: &str, t: &T) -> WriteResult {
+        self.insert_with_config(key, t, WriteConfig::default())
+    }
+
+    /// Write a single entry to the container with the given `WriteConfig`. See `WriteConfig` for information on the options.
+    ///
+    /// Entries that are written with `WriteConfig::magic` or `WriteConfig::type_fingerprint` must be read with `get_with_config`.
+    pub fn insert_with_config<'a, T: Serializable<'a>>(
+        &mut self,
+        key: &str,
+        t: &T,
+        config: WriteConfig,
+    ) -> WriteResult {
+        let bytes = crate::try_to_vec_with_config(t, config)?;
+        self.writer.write_all(&bytes)?;
+        self.index.push(IndexEntry {
+            key: String::from(key),
+            offset: self.position,
+            len: bytes.len() as u64,
+        });
+        self.position += bytes.len() as u64;
+        Ok(())
+    }
+
+    /// Write the index and footer, and return the underlying writer.
+    pub fn finish(mut self) -> WriteResult<W> {
+        let mut footer = Vec::new();
+        self.index.serialize(&mut footer)?;
+        self.position.serialize(&mut footer)?;
+        footer.extend_from_slice(&CONTAINER_MAGIC);
+        self.writer.write_all(&footer)?;
+        Ok(self.writer)
+    }
+}
+
+/// Parse the footer at the end of a container, returning the offset of the index.
+fn parse_footer(footer: &[u8]) -> ReadResult<u64> {
+    if footer.len() != FOOTER_SIZE || footer[8..] != CONTAINER_MAGIC {
+        return Err(ReadError::InvalidMagic);
+    }
+    let mut offset = [0u8; 8];
+    offset.copy_from_slice(&footer[..8]);
+    Ok(u64::from_be_bytes(offset))
+}
+
+fn parse_index(index: &[u8]) -> ReadResult<HashMap<String, (u64, u64)>> {
+    let config = ReadConfig {
+        error_on_trailing_bytes: true,
+    };
+    let entries: Vec<IndexEntry> = crate::helpers::deserialize_slice_without_header(
+        index,
+        VersionHeader::current(Version::new(0, 0, 0)),
+        config.into(),
+    )?;
+    Ok(entries
+        .into_iter()
+        .map(|entry| (entry.key, (entry.offset, entry.len)))
+        .collect())
+}
+
+/// A container that is completely loaded in memory. Entries are deserialized directly from the slice, so they can borrow from it.
+pub struct ContainerSlice<'a> {
+    slice: &'a [u8],
+    index: HashMap<String, (u64, u64)>,
+}
+
+impl<'a> ContainerSlice<'a> {
+    /// Read the index of the container in the given slice.
+    pub fn new(slice: &'a [u8]) -> ReadResult<Self> {
+        if slice.len() < CONTAINER_MAGIC.len() + FOOTER_SIZE
+            || slice[..CONTAINER_MAGIC.len()] != CONTAINER_MAGIC
+        {
+            return Err(ReadError::InvalidMagic);
+        }
+        let footer_start = slice.len() - FOOTER_SIZE;
+        let index_start = parse_footer(&slice[footer_start..])? as usize;
+        if index_start > footer_start {
+            return Err(ReadError::EndOfInput);
+        }
+        let index = parse_index(&slice[index_start..footer_start])?;
+        Ok(Self { slice, index })
+    }
+
+    /// The keys of all entries in this container.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.index.keys().map(String::as_str)
+    }
+
+    /// Get the raw bytes of the entry with the given key, including its version header.
+    pub fn entry(&self, key: &str) -> ReadResult<Option<&'a [u8]>> {
+        match self.index.get(key) {
+            Some(&(offset, len)) => {
+                let (offset, len) = (offset as usize, len as usize);
+                if self.slice.len() < offset.saturating_add(len) {
+                    return Err(ReadError::EndOfInput);
+                }
+                Ok(Some(&self.slice[offset..][..len]))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Deserialize the entry with the given key. Returns `Ok(None)` if the container does not contain the key.
+    pub fn get<T: Serializable<'a>>(&self, key: &str) -> ReadResult<Option<T>> {
+        self.get_with_config(key, ReadConfig::default())
+    }
+
+    /// Deserialize the entry with the given key with the given `ReadConfig`. See `ReadConfig` for information on the options.
+    pub fn get_with_config<T: Serializable<'a>>(
+        &self,
+        key: &str,
+        config: impl Into<ReadOptions>,
+    ) -> ReadResult<Option<T>> {
+        match self.entry(key)? {
+            Some(bytes) => crate::deserialize_slice_with_config(bytes, config).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+/// A container that is read from a seekable reader, e.g. a `std::fs::File`. Only the index is kept in memory.
+pub struct ContainerReader<R> {
+    reader: R,
+    index: HashMap<String, (u64, u64)>,
+}
+
+impl<R: Read + Seek> ContainerReader<R> {
+    /// Read the index of the container in the given reader.
+    pub fn new(mut reader: R) -> ReadResult<Self> {
+        let mut magic = [0u8; CONTAINER_MAGIC.len()];
+        reader.seek(SeekFrom::Start(0))?;
+        read_exact(&mut reader, &mut magic)?;
+        if magic != CONTAINER_MAGIC {
+            return Err(ReadError::InvalidMagic);
+        }
+
+        let mut footer = [0u8; FOOTER_SIZE];
+        let footer_start = reader.seek(SeekFrom::End(-(FOOTER_SIZE as i64)))?;
+        read_exact(&mut reader, &mut footer)?;
+        let index_start = parse_footer(&footer)?;
+        if index_start > footer_start {
+            return Err(ReadError::EndOfInput);
+        }
+
+        let mut index = vec![0u8; (footer_start - index_start) as usize];
+        reader.seek(SeekFrom::Start(index_start))?;
+        read_exact(&mut reader, &mut index)?;
+        let index = parse_index(&index)?;
+        Ok(Self { reader, index })
+    }
+
+    /// The keys of all entries in this container.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.index.keys().map(String::as_str)
+    }
+
+    /// Read and deserialize the entry with the given key. Returns `Ok(None)` if the container does not contain the key.
+    pub fn get<T: SerializableOwned>(&mut self, key: &str) -> ReadResult<Option<T>> {
+        self.get_with_config(key, ReadConfig::default())
+    }
+
+    /// Read and deserialize the entry with the given key with the given `ReadConfig`. See `ReadConfig` for information on the options.
+    pub fn get_with_config<T: SerializableOwned>(
+        &mut self,
+        key: &str,
+        config: impl Into<ReadOptions>,
+    ) -> ReadResult<Option<T>> {
+        let (offset, len) = match self.index.get(key) {
+            Some(&entry) => entry,
+            None => return Ok(None),
+        };
+        let mut bytes = Vec::new();
+        self.reader.seek(SeekFrom::Start(offset))?;
+        (&mut self.reader).take(len).read_to_end(&mut bytes)?;
+        if bytes.len() as u64 != len {
+            return Err(ReadError::EndOfInput);
+        }
+        crate::deserialize_slice_with_config(&bytes, config).map(Some)
+    }
+
+    /// Return the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+fn read_exact<R: Read>(reader: &mut R, bytes: &mut [u8]) -> ReadResult {
+    reader.read_exact(bytes).map_err(|e| match e.kind() {
+        std::io::ErrorKind::UnexpectedEof => ReadError::EndOfInput,
+        _ => ReadError::Io(e),
+    })
+}
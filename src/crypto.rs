@@ -0,0 +1,136 @@
+//! Authenticated encryption of documents with ChaCha20-Poly1305.
+//!
+//! An encrypted document starts with the regular version header, which is not encrypted so it can be inspected with `read_version` without the key.
+//! It is followed by a random 12-byte nonce and the encrypted object. The version header is authenticated as well,
+//! so changes to either the header or the encrypted object return `ReadError::DecryptionFailed`.
+//!
+//! ```rust
+//! # use binver::{crypto, Serializable};
+//! #[derive(Serializable, PartialEq, Debug)]
+//! pub struct Credentials {
+//!     #[since(0.0.1)]
+//!     pub user: String,
+//!     #[since(0.0.1)]
+//!     pub token: String,
+//! }
+//!
+//! let key = [7u8; crypto::KEY_SIZE];
+//! let credentials = Credentials {
+//!     user: String::from("Trangar"),
+//!     token: String::from("hunter2"),
+//! };
+//! let encrypted = crypto::to_vec_encrypted(&credentials, &key);
+//! assert_eq!(crypto::read_version(&encrypted).unwrap(), *binver::VERSION);
+//!
+//! let decrypted: Credentials = crypto::deserialize_slice_encrypted(&encrypted, &key).unwrap();
+//! assert_eq!(credentials, decrypted);
+//! ```
+
+use crate::{
+    helpers::ConfiguredWriter, ReadConfig, ReadError, ReadOptions, ReadResult, Serializable,
+    SerializableOwned, Version, WriteConfig, WriteError, WriteResult,
+};
+use alloc::vec::Vec;
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+    ChaCha20Poly1305, Key, Nonce,
+};
+
+/// The size of the key in bytes.
+pub const KEY_SIZE: usize = 32;
+
+/// The size of the nonce that is stored in every document, in bytes.
+pub const NONCE_SIZE: usize = 12;
+
+/// Serialize the given `Serializable` object to a vec, encrypting everything after the version header with the given key.
+///
+/// This panics if the object can not be written, use `to_vec_encrypted_with_config` to handle this error.
+pub fn to_vec_encrypted<'a, T: Serializable<'a>>(t: &T, key: &[u8; KEY_SIZE]) -> Vec<u8> {
+    to_vec_encrypted_with_config(t, key, WriteConfig::default()).unwrap()
+}
+
+/// Serialize the given `Serializable` object to a vec with the given `WriteConfig`, encrypting everything after the header with the given key.
+pub fn to_vec_encrypted_with_config<'a, T: Serializable<'a>>(
+    t: &T,
+    key: &[u8; KEY_SIZE],
+    config: WriteConfig,
+) -> WriteResult<Vec<u8>> {
+    let mut payload = Vec::new();
+    t.serialize(&mut ConfiguredWriter::new(&mut payload, &config)?)?;
+
+    let mut writer = Vec::new();
+    crate::helpers::write_header(
+        &mut ConfiguredWriter::new(&mut writer, &config)?,
+        &config,
+        T::FINGERPRINT,
+        false,
+    )?;
+
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(key));
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let encrypted = cipher
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: &payload,
+                aad: &writer,
+            },
+        )
+        .map_err(|_| WriteError::EncryptionFailed)?;
+    writer.extend_from_slice(&nonce);
+    writer.extend_from_slice(&encrypted);
+    Ok(writer)
+}
+
+/// Read the version of an encrypted document, without decrypting it.
+///
+/// Use `read_version_with_config` if the document was written with `WriteConfig::magic`.
+pub fn read_version(slice: &[u8]) -> ReadResult<Version> {
+    read_version_with_config(slice, ReadConfig::default())
+}
+
+/// Read the version of an encrypted document with the given `ReadConfig`, without decrypting it.
+///
+/// If `ReadOptions::with_magic` is set, the magic bytes are checked and skipped.
+pub fn read_version_with_config(
+    slice: &[u8],
+    config: impl Into<ReadOptions>,
+) -> ReadResult<Version> {
+    let slice = crate::helpers::strip_magic(slice, &config.into())?;
+    crate::helpers::split_version_header(slice).map(|(header, _)| header.version)
+}
+
+/// Deserialize an object from a slice that was serialized with `to_vec_encrypted`.
+pub fn deserialize_slice_encrypted<T: SerializableOwned>(
+    slice: &[u8],
+    key: &[u8; KEY_SIZE],
+) -> ReadResult<T> {
+    deserialize_slice_encrypted_with_config(slice, key, ReadConfig::default())
+}
+
+/// Deserialize an object from an encrypted slice with the given `ReadConfig`. See `ReadConfig` for information on the options.
+pub fn deserialize_slice_encrypted_with_config<T: SerializableOwned>(
+    slice: &[u8],
+    key: &[u8; KEY_SIZE],
+    config: impl Into<ReadOptions>,
+) -> ReadResult<T> {
+    let config = config.into();
+    let (header, tables, remaining) = crate::helpers::read_header(slice, &config, T::FINGERPRINT)?;
+    if remaining.len() < NONCE_SIZE {
+        return Err(ReadError::EndOfInput);
+    }
+    let aad = &slice[..slice.len() - remaining.len()];
+    let (nonce, encrypted) = remaining.split_at(NONCE_SIZE);
+
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(key));
+    let payload = cipher
+        .decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: encrypted,
+                aad,
+            },
+        )
+        .map_err(|_| ReadError::DecryptionFailed)?;
+    crate::helpers::deserialize_slice_with_header(&payload, header, tables, config)
+}
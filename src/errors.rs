@@ -3,6 +3,36 @@
 pub enum WriteError {
     /// Could not fit the entire object into the given reader.
     EndOfOutput,
+
+    /// The version can not be represented in the wire format revision that is being written.
+    /// Before revision 2, versions could only contain `u16` components without pre-release or build metadata.
+    VersionOutOfRange(crate::Version),
+
+    /// Tried to write a wire format revision that is newer than `WIRE_REVISION`, or that does not support the options in the `WriteConfig`.
+    UnsupportedWireRevision(u8),
+
+    /// Tried to write a frame that is larger than the configured maximum frame size.
+    FrameTooLarge(usize),
+
+    /// Tried to write a chunk of which the body does not fit in the `u32` length of a chunk.
+    ChunkTooLarge(usize),
+
+    /// Tried to write an enum variant with a `#[binver(feature = "..")]` attribute, but the feature is not enabled in `WriteConfig::features`.
+    FeatureDisabled(&'static str),
+
+    /// The data could not be encrypted, e.g. because it is too large for the cipher.
+    EncryptionFailed,
+
+    #[cfg(feature = "std")]
+    /// The underlying `std::io` writer returned an error.
+    Io(std::io::Error),
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for WriteError {
+    fn from(e: std::io::Error) -> Self {
+        WriteError::Io(e)
+    }
 }
 
 /// Error thrown while reading
@@ -26,4 +56,70 @@ pub enum ReadError {
 
     /// Throws an error when `Reader::read_slice` is called but the reader does not have a persistent buffer.
     ReaderNotPersistent,
+
+    /// Encountered a frame that is larger than the configured maximum frame size.
+    FrameTooLarge(usize),
+
+    /// The checksum at the end of the data does not match the checksum of the data itself. The data was most likely corrupted or truncated.
+    ChecksumMismatch {
+        /// The checksum that was stored in the data
+        expected: u32,
+        /// The checksum of the data that was read
+        found: u32,
+    },
+
+    /// Encountered compressed data with a codec that is not supported, or a compressed document when the `compression` feature is disabled.
+    UnknownCodec(u8),
+
+    /// Compressed data could not be decompressed, or was larger than its recorded length.
+    DecompressionFailed,
+
+    /// A document was read as compressed, but its header does not mark it as compressed.
+    NotCompressed,
+
+    /// Encrypted data could not be decrypted. Either the key is wrong, or the data has been tampered with.
+    DecryptionFailed,
+
+    /// The type fingerprint in the header does not match the type that is being read. The data was most likely written as a different type.
+    TypeMismatch {
+        /// The `Serializable::FINGERPRINT` of the type that is being read
+        expected: u64,
+        /// The fingerprint that was stored in the header
+        found: u64,
+    },
+
+    /// A variable length integer was longer than allowed, or did not fit in the integer type being read.
+    InvalidVarint,
+
+    /// A `usize` or `isize` was read that does not fit in the `usize` or `isize` of this platform.
+    IntegerOutOfRange(i128),
+
+    /// While reading a `char`, encountered a value that is not a valid unicode scalar value.
+    InvalidChar(u32),
+
+    /// The pre-release or build metadata of a version is not valid.
+    InvalidVersion,
+
+    /// The data was written with a newer revision of the binver wire format than this version of binver can read.
+    UnsupportedWireRevision(u8),
+
+    /// The data did not start or end with the expected magic bytes.
+    InvalidMagic,
+
+    /// Encountered an entry in a record log that is not a version or a record.
+    InvalidRecordKind(u8),
+
+    /// Encountered a record in a record log before the first version entry.
+    MissingRecordVersion,
+
+    #[cfg(feature = "std")]
+    /// The underlying `std::io` reader returned an error.
+    Io(std::io::Error),
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for ReadError {
+    fn from(e: std::io::Error) -> Self {
+        ReadError::Io(e)
+    }
 }
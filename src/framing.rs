@@ -0,0 +1,246 @@
+//! Length-delimited frames for sending multiple objects over a single stream.
+//!
+//! Every frame starts with a big-endian `u32` containing the length of the frame body. The body contains the version header,
+//! unless `FrameCodec::version_header` is disabled, followed by the serialized object.
+//!
+//! ```rust
+//! # use binver::{framing::FrameCodec, Serializable};
+//! #[derive(Serializable, PartialEq, Debug)]
+//! pub struct Ping {
+//!     #[since(0.0.1)]
+//!     pub id: u32,
+//! }
+//!
+//! let codec = FrameCodec::<Ping>::new();
+//! let mut buffer = Vec::new();
+//! codec.encode(&Ping { id: 1 }, &mut buffer).unwrap();
+//! codec.encode(&Ping { id: 2 }, &mut buffer).unwrap();
+//!
+//! assert_eq!(codec.decode(&mut buffer).unwrap(), Some(Ping { id: 1 }));
+//! assert_eq!(codec.decode(&mut buffer).unwrap(), Some(Ping { id: 2 }));
+//! assert_eq!(codec.decode(&mut buffer).unwrap(), None);
+//! ```
+
+use crate::{
+    helpers::VersionHeader, ReadError, ReadOptions, ReadResult, Serializable, SerializableOwned,
+    Version, WriteConfig, WriteError, WriteResult,
+};
+use alloc::vec::Vec;
+use core::{fmt, marker::PhantomData};
+use std::io::{self, Read, Write};
+
+/// The amount of bytes used for the length prefix of every frame.
+pub const FRAME_LENGTH_SIZE: usize = 4;
+
+/// The default value of `FrameCodec::max_frame_size`, 8 MiB.
+pub const DEFAULT_MAX_FRAME_SIZE: usize = 8 * 1024 * 1024;
+
+/// Encoder and decoder for length-delimited frames containing a `T`.
+///
+/// When the `tokio-codec` feature is enabled, this implements `tokio_util::codec::{Encoder, Decoder}`.
+pub struct FrameCodec<T> {
+    /// The maximum length of a frame body. Larger frames will return `WriteError::FrameTooLarge` or `ReadError::FrameTooLarge`.
+    pub max_frame_size: usize,
+    /// If `true` (the default), every frame contains its own version header.
+    pub version_header: bool,
+    /// The version used to read frames when `version_header` is `false`.
+    pub version: Version,
+    /// The config used to serialize the body of every frame, e.g. to set the schema version in the header.
+    ///
+    /// The header options are ignored when `version_header` is `false`. The codec is not tied to a lifetime, so the domains and features must be `'static`.
+    pub write_config: WriteConfig<'static>,
+    /// The options used to deserialize the body of every frame. A `ReadConfig` can be converted with `.into()`.
+    pub read_config: ReadOptions,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> FrameCodec<T> {
+    /// Create a new codec with the default settings.
+    pub fn new() -> Self {
+        Self {
+            max_frame_size: DEFAULT_MAX_FRAME_SIZE,
+            version_header: true,
+            version: crate::VERSION.clone(),
+            write_config: WriteConfig::default(),
+            read_config: ReadOptions::default(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Serialize the given object as a single frame, and append it to the given buffer.
+    pub fn encode<'a>(&self, t: &T, buffer: &mut Vec<u8>) -> WriteResult
+    where
+        T: Serializable<'a>,
+    {
+        // Reject frames that are too large before serializing them
+        let len = if self.version_header {
+            crate::serialized_size_with_config(t, self.write_config.clone())?
+        } else {
+            crate::serialized_size_without_header(t)?
+        };
+        if len > self.max_frame_size || len > u32::MAX as usize {
+            return Err(WriteError::FrameTooLarge(len));
+        }
+
+        let start = buffer.len();
+        buffer.reserve(FRAME_LENGTH_SIZE + len);
+        buffer.extend_from_slice(&(len as u32).to_be_bytes());
+        if self.version_header {
+            crate::helpers::write_with_config(buffer, t, &self.write_config)?;
+        } else {
+            t.serialize(buffer)?;
+        }
+        debug_assert_eq!(buffer.len() - start - FRAME_LENGTH_SIZE, len);
+        Ok(())
+    }
+
+    /// Serialize the given object as a single frame, and write it to the given writer.
+    pub fn write_to<'a, W: Write + ?Sized>(&self, writer: &mut W, t: &T) -> WriteResult
+    where
+        T: Serializable<'a>,
+    {
+        let mut buffer = Vec::new();
+        self.encode(t, &mut buffer)?;
+        writer.write_all(&buffer)?;
+        Ok(())
+    }
+
+    fn parse_frame_length(&self, bytes: [u8; FRAME_LENGTH_SIZE]) -> ReadResult<usize> {
+        let len = u32::from_be_bytes(bytes) as usize;
+        if len > self.max_frame_size {
+            return Err(ReadError::FrameTooLarge(len));
+        }
+        Ok(len)
+    }
+
+    /// Get the length of the first frame body in the given buffer, if the length prefix has been received.
+    fn frame_length(&self, buffer: &[u8]) -> ReadResult<Option<usize>> {
+        if buffer.len() < FRAME_LENGTH_SIZE {
+            return Ok(None);
+        }
+        let mut bytes = [0u8; FRAME_LENGTH_SIZE];
+        bytes.copy_from_slice(&buffer[..FRAME_LENGTH_SIZE]);
+        self.parse_frame_length(bytes).map(Some)
+    }
+
+    fn decode_body(&self, body: &[u8]) -> ReadResult<T>
+    where
+        T: SerializableOwned,
+    {
+        if self.version_header {
+            crate::deserialize_slice_with_config(body, self.read_config.clone())
+        } else {
+            crate::helpers::deserialize_slice_without_header(
+                body,
+                VersionHeader::current(self.version.clone()),
+                self.read_config.clone(),
+            )
+        }
+    }
+
+    /// Decode the first frame in the given buffer, and remove it from the buffer.
+    ///
+    /// If the buffer does not contain a complete frame yet, `Ok(None)` is returned and the buffer is left untouched.
+    pub fn decode(&self, buffer: &mut Vec<u8>) -> ReadResult<Option<T>>
+    where
+        T: SerializableOwned,
+    {
+        let len = match self.frame_length(buffer)? {
+            Some(len) if buffer.len() >= FRAME_LENGTH_SIZE + len => len,
+            _ => return Ok(None),
+        };
+        let result = self.decode_body(&buffer[FRAME_LENGTH_SIZE..][..len]);
+        buffer.drain(..FRAME_LENGTH_SIZE + len);
+        result.map(Some)
+    }
+
+    /// Read a single frame from the given reader.
+    ///
+    /// Returns `Ok(None)` if the reader is at the end of the stream before the start of a frame.
+    pub fn read_from<R: Read + ?Sized>(&self, reader: &mut R) -> ReadResult<Option<T>>
+    where
+        T: SerializableOwned,
+    {
+        let mut header = [0u8; FRAME_LENGTH_SIZE];
+        let mut read = 0;
+        while read < FRAME_LENGTH_SIZE {
+            match reader.read(&mut header[read..]) {
+                Ok(0) if read == 0 => return Ok(None),
+                Ok(0) => return Err(ReadError::EndOfInput),
+                Ok(n) => read += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(ReadError::Io(e)),
+            }
+        }
+        let len = self.parse_frame_length(header)?;
+        let mut body = alloc::vec![0u8; len];
+        reader.read_exact(&mut body).map_err(|e| match e.kind() {
+            io::ErrorKind::UnexpectedEof => ReadError::EndOfInput,
+            _ => ReadError::Io(e),
+        })?;
+        self.decode_body(&body).map(Some)
+    }
+}
+
+impl<T> Default for FrameCodec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for FrameCodec<T> {
+    fn clone(&self) -> Self {
+        Self {
+            max_frame_size: self.max_frame_size,
+            version_header: self.version_header,
+            version: self.version.clone(),
+            write_config: self.write_config.clone(),
+            read_config: self.read_config.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for FrameCodec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FrameCodec")
+            .field("max_frame_size", &self.max_frame_size)
+            .field("version_header", &self.version_header)
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
+#[cfg(feature = "tokio-codec")]
+impl<T: SerializableOwned> tokio_util::codec::Decoder for FrameCodec<T> {
+    type Item = T;
+    type Error = ReadError;
+
+    fn decode(&mut self, src: &mut bytes::BytesMut) -> ReadResult<Option<T>> {
+        use bytes::Buf;
+
+        let len = match self.frame_length(src)? {
+            Some(len) => len,
+            None => return Ok(None),
+        };
+        if src.len() < FRAME_LENGTH_SIZE + len {
+            src.reserve(FRAME_LENGTH_SIZE + len - src.len());
+            return Ok(None);
+        }
+        let result = self.decode_body(&src[FRAME_LENGTH_SIZE..][..len]);
+        src.advance(FRAME_LENGTH_SIZE + len);
+        result.map(Some)
+    }
+}
+
+#[cfg(feature = "tokio-codec")]
+impl<T: SerializableOwned> tokio_util::codec::Encoder<T> for FrameCodec<T> {
+    type Error = WriteError;
+
+    fn encode(&mut self, item: T, dst: &mut bytes::BytesMut) -> WriteResult {
+        let mut buffer = Vec::new();
+        FrameCodec::encode(self, &item, &mut buffer)?;
+        dst.extend_from_slice(&buffer);
+        Ok(())
+    }
+}
@@ -1,28 +1,433 @@
 use crate::{
-    ReadConfig, ReadError, ReadResult, Reader, Serializable, Version, WriteError, WriteResult,
-    Writer,
+    implementations::{read_varint, write_identifier, write_varint},
+    ByteOrder, IntegerEncoding, ReadConfig, ReadError, ReadOptions, ReadResult, Reader,
+    Serializable, Version, WriteConfig, WriteError, WriteResult, Writer,
 };
 #[cfg(feature = "std")]
 use alloc::vec::Vec;
 
+/// Magic bytes that can be set as `WriteConfig::magic`, so the data can be recognized by `sniff`.
+pub const MAGIC: &[u8] = b"BINVER";
+
+/// The revision of the binver wire format that is written by this version of binver.
+///
+/// This is incremented whenever binver changes how data is encoded. Data written with an older revision can still be read,
+/// `Reader::wire_revision` returns the revision of the data being read.
+///
+/// Revision 0 is the format from before the revision was written in the header. It is recognized because its header starts with a `0` byte.
+/// Revision 2 encodes versions with 64-bit components, pre-release and build metadata, where older revisions only support `u16` components.
+/// Revision 3 encodes `usize` and `isize` as 64-bit integers, where older revisions use the width of the platform that wrote the data.
+pub const WIRE_REVISION: u8 = 3;
+
+/// Set in the revision byte of the header when the version is followed by a table of domain versions. See `WriteConfig::domains`.
+const FLAG_DOMAINS: u8 = 0x80;
+
+/// Set in the revision byte of the header when it contains a table of enabled features. See `WriteConfig::features`.
+const FLAG_FEATURES: u8 = 0x40;
+
+/// Set in the revision byte of the header when integers are written as varints. See `WriteConfig::integer_encoding`.
+const FLAG_VARINT: u8 = 0x20;
+
+/// Set in the revision byte of the header when integers are written in little-endian byte order. See `WriteConfig::byte_order`.
+const FLAG_LITTLE_ENDIAN: u8 = 0x10;
+
+/// Set in the revision byte of the header when everything after the header is compressed. See `compression::to_vec_compressed`.
+const FLAG_COMPRESSED: u8 = 0x08;
+
+/// The bits of the revision byte in the header that contain the revision. The other bits are flags, which are supported since revision 2.
+const REVISION_MASK: u8 = 0x07;
+
+/// The tables and options in the header of a document, which have been validated by `split_header`.
+#[derive(Clone, Default)]
+pub(crate) struct HeaderTables<'a> {
+    /// The domains are parsed once, so types of a domain don't parse the table every time they are read.
+    #[cfg(feature = "std")]
+    domains: Vec<(&'a [u8], Version)>,
+    /// Without an allocator, the table is searched every time a domain is looked up.
+    #[cfg(not(feature = "std"))]
+    domains: &'a [u8],
+    features: &'a [u8],
+    integer_encoding: IntegerEncoding,
+    byte_order: ByteOrder,
+    compressed: bool,
+}
+
+#[cfg(feature = "compression")]
+impl HeaderTables<'_> {
+    /// If everything after the header is compressed.
+    pub fn is_compressed(&self) -> bool {
+        self.compressed
+    }
+}
+
+/// The wire format revision and schema version of the data being read.
+#[derive(Clone, Debug)]
+pub(crate) struct VersionHeader {
+    pub revision: u8,
+    pub version: Version,
+}
+
+impl VersionHeader {
+    /// A header for data that is written with the current wire format revision.
+    pub fn current(version: Version) -> Self {
+        Self {
+            revision: WIRE_REVISION,
+            version,
+        }
+    }
+}
+
 #[cfg(feature = "std")]
 /// Serialize the given `Serialiazable` object to a vec
+///
+/// This panics if the object can not be written, e.g. an enum variant of which the feature is not enabled. Use `try_to_vec` to handle this error.
 pub fn to_vec<'a, T: Serializable<'a>>(t: &T) -> Vec<u8> {
-    let mut writer = Vec::<u8>::new();
-    crate::VERSION.serialize(&mut writer).unwrap();
-    t.serialize(&mut writer).unwrap();
-    writer
+    to_vec_with_config(t, WriteConfig::default())
+}
+
+#[cfg(feature = "std")]
+/// Serialize the given `Serializable` object to a vec with the given `WriteConfig`. See `WriteConfig` for information on the options.
+///
+/// This panics if the object can not be written with the given config, use `try_to_vec_with_config` to handle this error.
+pub fn to_vec_with_config<'a, T: Serializable<'a>>(t: &T, config: WriteConfig) -> Vec<u8> {
+    try_to_vec_with_config(t, config).unwrap()
+}
+
+#[cfg(feature = "std")]
+/// Serialize the given `Serializable` object to a vec, returning an error if the object can not be written.
+pub fn try_to_vec<'a, T: Serializable<'a>>(t: &T) -> WriteResult<Vec<u8>> {
+    try_to_vec_with_config(t, WriteConfig::default())
+}
+
+#[cfg(feature = "std")]
+/// Serialize the given `Serializable` object to a vec with the given `WriteConfig`, returning an error if the object can not be written.
+pub fn try_to_vec_with_config<'a, T: Serializable<'a>>(
+    t: &T,
+    config: WriteConfig,
+) -> WriteResult<Vec<u8>> {
+    // Only types with a maximum size are preallocated, so other types are not serialized twice
+    let mut vec = Vec::<u8>::with_capacity(T::MAX_SERIALIZED_SIZE.unwrap_or(0));
+    write_with_config(&mut vec, t, &config)?;
+    Ok(vec)
 }
 
 /// Serialize the given `Serializable` object to the given slice. The amount of bytes written is returned.
 pub fn write_to_slice<'a, T: Serializable<'a>>(slice: &mut [u8], t: &T) -> WriteResult<usize> {
+    write_to_slice_with_config(slice, t, WriteConfig::default())
+}
+
+/// Serialize the given `Serializable` object to the given slice with the given `WriteConfig`. See `WriteConfig` for information on the options.
+pub fn write_to_slice_with_config<'a, T: Serializable<'a>>(
+    slice: &mut [u8],
+    t: &T,
+    config: WriteConfig,
+) -> WriteResult<usize> {
     let mut writer = SliceWriter { slice, index: 0 };
-    crate::VERSION.serialize(&mut writer)?;
+    write_with_config(&mut writer, t, &config)?;
+    Ok(writer.index)
+}
+
+/// Write the header of the given `WriteConfig`, followed by the given object written with the options of the config.
+pub(crate) fn write_with_config<'a, T: Serializable<'a>, W: Writer + ?Sized>(
+    writer: &mut W,
+    t: &T,
+    config: &WriteConfig,
+) -> WriteResult {
+    let mut writer = ConfiguredWriter::new(writer, config)?;
+    write_header(&mut writer, config, T::FINGERPRINT, false)?;
+    t.serialize(&mut writer)
+}
+
+#[cfg(feature = "std")]
+/// Serialize the given `Serializable` object to a vec, without a version header.
+///
+/// This is useful when the schema version is already stored elsewhere, e.g. in a database column or another protocol.
+/// The data can only be read with `deserialize_slice_with_version`, given the version of the data.
+pub fn to_vec_without_header<'a, T: Serializable<'a>>(t: &T) -> WriteResult<Vec<u8>> {
+    let mut writer = Vec::<u8>::with_capacity(T::MAX_SERIALIZED_SIZE.unwrap_or(0));
     t.serialize(&mut writer)?;
+    Ok(writer)
+}
 
+/// Serialize the given `Serializable` object to the given slice, without a version header. The amount of bytes written is returned.
+///
+/// See `to_vec_without_header` for more information.
+pub fn write_to_slice_without_header<'a, T: Serializable<'a>>(
+    slice: &mut [u8],
+    t: &T,
+) -> WriteResult<usize> {
+    let mut writer = SliceWriter { slice, index: 0 };
+    t.serialize(&mut writer)?;
     Ok(writer.index)
 }
 
+/// Serialize the given `Serializable` object to an array, without a version header. Bytes after the serialized object are `0`.
+///
+/// The size of the array is checked at compile time: `N` must be at least `T::MAX_SERIALIZED_SIZE`, and types without a maximum size can not be used.
+/// The data can be read with `deserialize_slice_with_version`, see `to_vec_without_header` for more information.
+///
+/// ```rust
+/// # use binver::{Serializable, Version};
+/// #[derive(Serializable, Debug, PartialEq)]
+/// pub struct Telemetry {
+///     #[since(0.0.1)]
+///     pub temperature: i16,
+///     #[since(0.0.1)]
+///     pub uptime: u32,
+/// }
+///
+/// let telemetry = Telemetry { temperature: -5, uptime: 3600 };
+/// let bytes: [u8; 6] = binver::encode_to_array(&telemetry).unwrap();
+/// let deserialized: Telemetry = binver::deserialize_slice_with_version(&bytes, Version::new(0, 0, 1)).unwrap();
+/// assert_eq!(deserialized, telemetry);
+/// ```
+pub fn encode_to_array<'a, T: Serializable<'a>, const N: usize>(t: &T) -> WriteResult<[u8; N]> {
+    const {
+        assert!(
+            matches!(T::MAX_SERIALIZED_SIZE, Some(max) if max <= N),
+            "the array is smaller than `Serializable::MAX_SERIALIZED_SIZE`"
+        )
+    };
+    let mut array = [0u8; N];
+    write_to_slice_without_header(&mut array, t)?;
+    Ok(array)
+}
+
+/// Return the amount of bytes that `to_vec` or `write_to_slice` write for the given object, without writing it.
+///
+/// This can be used to allocate a buffer of the exact size, or to reject objects that are too large before serializing them.
+pub fn serialized_size<'a, T: Serializable<'a>>(t: &T) -> WriteResult<usize> {
+    serialized_size_with_config(t, WriteConfig::default())
+}
+
+/// Return the amount of bytes that `to_vec_with_config` or `write_to_slice_with_config` write for the given object and `WriteConfig`.
+pub fn serialized_size_with_config<'a, T: Serializable<'a>>(
+    t: &T,
+    config: WriteConfig,
+) -> WriteResult<usize> {
+    let mut size: SizeWriter = SizeWriter::new(None);
+    let mut writer = ConfiguredWriter::new(&mut size, &config)?;
+    write_header(&mut writer, &config, T::FINGERPRINT, false)?;
+    t.serialize(&mut writer)?;
+    Ok(size.size)
+}
+
+/// Return the amount of bytes that `to_vec_without_header` or `write_to_slice_without_header` write for the given object.
+pub fn serialized_size_without_header<'a, T: Serializable<'a>>(t: &T) -> WriteResult<usize> {
+    let mut size: SizeWriter = SizeWriter::new(None);
+    t.serialize(&mut size)?;
+    Ok(size.size)
+}
+
+/// A writer that applies the wire revision, features, integer encoding, byte order and NaN handling of a `WriteConfig`.
+pub(crate) struct ConfiguredWriter<'w, W: ?Sized> {
+    inner: &'w mut W,
+    revision: u8,
+    features: &'w [&'w str],
+    integer_encoding: IntegerEncoding,
+    byte_order: ByteOrder,
+    canonical_nan: bool,
+}
+
+impl<'w, W: Writer + ?Sized> ConfiguredWriter<'w, W> {
+    pub fn new(inner: &'w mut W, config: &WriteConfig<'w>) -> WriteResult<Self> {
+        let revision = config.wire_revision.unwrap_or(WIRE_REVISION);
+        if revision > WIRE_REVISION {
+            return Err(WriteError::UnsupportedWireRevision(revision));
+        }
+        Ok(Self {
+            inner,
+            revision,
+            features: config.features,
+            integer_encoding: config.integer_encoding,
+            byte_order: config.byte_order,
+            canonical_nan: config.canonical_nan,
+        })
+    }
+}
+
+impl<'w, W: Writer + ?Sized> Writer for ConfiguredWriter<'w, W> {
+    fn write(&mut self, bytes: &[u8]) -> WriteResult {
+        self.inner.write(bytes)
+    }
+
+    fn wire_revision(&self) -> u8 {
+        self.revision
+    }
+
+    fn has_feature(&self, feature: &str) -> bool {
+        self.features.contains(&feature)
+    }
+
+    fn integer_encoding(&self) -> IntegerEncoding {
+        self.integer_encoding
+    }
+
+    fn byte_order(&self) -> ByteOrder {
+        self.byte_order
+    }
+
+    fn canonical_nan(&self) -> bool {
+        self.canonical_nan
+    }
+
+    fn position(&self) -> Option<usize> {
+        self.inner.position()
+    }
+
+    fn insert(&mut self, position: usize, bytes: &[u8]) -> WriteResult {
+        self.inner.insert(position, bytes)
+    }
+}
+
+/// Write the header of the given `WriteConfig`. If `compressed` is set, the header marks everything after it as compressed.
+pub(crate) fn write_header<W: Writer + ?Sized>(
+    writer: &mut W,
+    config: &WriteConfig,
+    fingerprint: u64,
+    compressed: bool,
+) -> WriteResult {
+    if let Some(magic) = config.magic {
+        writer.write(magic)?;
+    }
+    let version = config.schema_version.as_ref().unwrap_or(&crate::VERSION);
+    let mut flags = 0;
+    if !config.domains.is_empty() {
+        flags |= FLAG_DOMAINS;
+    }
+    if !config.features.is_empty() {
+        flags |= FLAG_FEATURES;
+    }
+    if config.integer_encoding == IntegerEncoding::Varint {
+        flags |= FLAG_VARINT;
+    }
+    if config.byte_order == ByteOrder::LittleEndian {
+        flags |= FLAG_LITTLE_ENDIAN;
+    }
+    if compressed {
+        flags |= FLAG_COMPRESSED;
+    }
+    if flags == 0 {
+        write_version_header(writer, version)?;
+    } else {
+        let revision = writer.wire_revision();
+        if revision < 2 {
+            return Err(WriteError::UnsupportedWireRevision(revision));
+        }
+        (revision | flags).serialize(writer)?;
+        version.serialize(writer)?;
+        if flags & FLAG_DOMAINS != 0 {
+            write_varint(writer, config.domains.len() as u64)?;
+            for (domain, version) in config.domains {
+                write_identifier(writer, domain)?;
+                version.serialize(writer)?;
+            }
+        }
+        if flags & FLAG_FEATURES != 0 {
+            write_varint(writer, config.features.len() as u64)?;
+            for feature in config.features {
+                write_identifier(writer, feature)?;
+            }
+        }
+    }
+    if config.type_fingerprint {
+        // The fingerprint is always written with its full width in big-endian, so it can be checked before the options in the header are known
+        writer.write(&fingerprint.to_be_bytes())?;
+    }
+    Ok(())
+}
+
+/// Check if the given bytes start with `binver::MAGIC`, and return the version of the data.
+///
+/// This only recognizes data that was written with `WriteConfig::magic` set to `binver::MAGIC`.
+pub fn sniff(bytes: &[u8]) -> Option<Version> {
+    sniff_with_magic(bytes, MAGIC)
+}
+
+/// Check if the given bytes start with the given magic bytes, and return the version of the data.
+pub fn sniff_with_magic(bytes: &[u8], magic: &[u8]) -> Option<Version> {
+    let bytes = bytes.strip_prefix(magic)?;
+    split_header(bytes)
+        .ok()
+        .map(|(header, _, _)| header.version)
+}
+
+#[cfg(all(feature = "std", feature = "checksum"))]
+/// Serialize the given `Serializable` object to a vec, followed by a CRC32 checksum of the serialized bytes.
+///
+/// Use `deserialize_slice_checked` to deserialize this, which returns `ReadError::ChecksumMismatch` if the data was corrupted.
+pub fn to_vec_checked<'a, T: Serializable<'a>>(t: &T) -> WriteResult<Vec<u8>> {
+    to_vec_checked_with_config(t, WriteConfig::default())
+}
+
+#[cfg(all(feature = "std", feature = "checksum"))]
+/// Serialize the given `Serializable` object to a vec with the given `WriteConfig`, followed by a CRC32 checksum of the serialized bytes.
+pub fn to_vec_checked_with_config<'a, T: Serializable<'a>>(
+    t: &T,
+    config: WriteConfig,
+) -> WriteResult<Vec<u8>> {
+    let mut writer = try_to_vec_with_config(t, config)?;
+    let checksum = crc32fast::hash(&writer);
+    writer.extend_from_slice(&checksum.to_be_bytes());
+    Ok(writer)
+}
+
+#[cfg(feature = "checksum")]
+/// Serialize the given `Serializable` object to the given slice, followed by a CRC32 checksum of the serialized bytes. The amount of bytes written is returned.
+pub fn write_to_slice_checked<'a, T: Serializable<'a>>(
+    slice: &mut [u8],
+    t: &T,
+) -> WriteResult<usize> {
+    write_to_slice_checked_with_config(slice, t, WriteConfig::default())
+}
+
+#[cfg(feature = "checksum")]
+/// Serialize the given `Serializable` object to the given slice with the given `WriteConfig`, followed by a CRC32 checksum of the serialized bytes.
+/// The amount of bytes written is returned.
+pub fn write_to_slice_checked_with_config<'a, T: Serializable<'a>>(
+    slice: &mut [u8],
+    t: &T,
+    config: WriteConfig,
+) -> WriteResult<usize> {
+    let len = write_to_slice_with_config(slice, t, config)?;
+    let checksum = crc32fast::hash(&slice[..len]);
+    // The checksum is always big-endian, regardless of the `WriteConfig`
+    slice
+        .get_mut(len..len + CHECKSUM_SIZE)
+        .ok_or(WriteError::EndOfOutput)?
+        .copy_from_slice(&checksum.to_be_bytes());
+    Ok(len + CHECKSUM_SIZE)
+}
+
+#[cfg(feature = "checksum")]
+/// Deserialize an object from the given slice, that was serialized with `to_vec_checked` or `write_to_slice_checked`.
+pub fn deserialize_slice_checked<'a, T: Serializable<'a>>(slice: &'a [u8]) -> ReadResult<T> {
+    deserialize_slice_checked_with_config(slice, ReadConfig::default())
+}
+
+#[cfg(feature = "checksum")]
+/// Deserialize an object from the given slice with a checksum, with the given `ReadConfig`. See `ReadConfig` for information on the options.
+pub fn deserialize_slice_checked_with_config<'a, T: Serializable<'a>>(
+    slice: &'a [u8],
+    config: impl Into<ReadOptions>,
+) -> ReadResult<T> {
+    if slice.len() < CHECKSUM_SIZE {
+        return Err(ReadError::EndOfInput);
+    }
+    let (slice, checksum) = slice.split_at(slice.len() - CHECKSUM_SIZE);
+    let mut expected = [0u8; CHECKSUM_SIZE];
+    expected.copy_from_slice(checksum);
+    let expected = u32::from_be_bytes(expected);
+    let found = crc32fast::hash(slice);
+    if expected != found {
+        return Err(ReadError::ChecksumMismatch { expected, found });
+    }
+    deserialize_slice_with_config(slice, config)
+}
+
+#[cfg(feature = "checksum")]
+const CHECKSUM_SIZE: usize = 4;
+
 /// Deserialize an object from the given slice.
 pub fn deserialize_slice<'a, T: Serializable<'a>>(slice: &'a [u8]) -> ReadResult<T> {
     deserialize_slice_with_config(slice, ReadConfig::default())
@@ -31,21 +436,193 @@ pub fn deserialize_slice<'a, T: Serializable<'a>>(slice: &'a [u8]) -> ReadResult
 /// Deserialize an object from the given slice with the given `ReadConfig`. See `ReadConfig` for information on the options.
 pub fn deserialize_slice_with_config<'a, T: Serializable<'a>>(
     slice: &'a [u8],
-    config: ReadConfig,
+    config: impl Into<ReadOptions>,
 ) -> ReadResult<T> {
-    let mut reader = SliceReader {
-        version: Version::new(0, 0, 0),
-        slice,
-        index: 0,
+    let config = config.into();
+    let (header, tables, slice) = read_header(slice, &config, T::FINGERPRINT)?;
+    deserialize_slice_with_header(slice, header, tables, config)
+}
+
+/// Deserialize an object from the given slice that was written without a version header, as the given version.
+///
+/// The data must be written with the current `WIRE_REVISION`, e.g. with `to_vec_without_header`.
+pub fn deserialize_slice_with_version<'a, T: Serializable<'a>>(
+    slice: &'a [u8],
+    version: Version,
+) -> ReadResult<T> {
+    deserialize_slice_with_version_and_config(slice, version, ReadConfig::default())
+}
+
+/// Deserialize an object from the given slice without a version header, with the given `ReadConfig`. See `ReadConfig` for information on the options.
+///
+/// The magic bytes and type fingerprint are part of the header, so `ReadOptions::with_magic` and `ReadOptions::with_type_fingerprint` are ignored.
+pub fn deserialize_slice_with_version_and_config<'a, T: Serializable<'a>>(
+    slice: &'a [u8],
+    version: Version,
+    config: impl Into<ReadOptions>,
+) -> ReadResult<T> {
+    deserialize_slice_without_header(slice, VersionHeader::current(version), config.into())
+}
+
+/// Check the magic bytes and type fingerprint of the given `ReadOptions`, and read the version header.
+///
+/// Returns the header, the tables in the header and the remaining bytes.
+pub(crate) fn read_header<'s>(
+    slice: &'s [u8],
+    config: &ReadOptions,
+    fingerprint: u64,
+) -> ReadResult<(VersionHeader, HeaderTables<'s>, &'s [u8])> {
+    let (header, tables, slice) = split_header(strip_magic(slice, config)?)?;
+    if !config.type_fingerprint {
+        return Ok((header, tables, slice));
+    }
+    let mut reader = SliceReader::new(slice, header.clone());
+    let found = u64::deserialize(&mut reader)?;
+    if found != fingerprint {
+        return Err(ReadError::TypeMismatch {
+            expected: fingerprint,
+            found,
+        });
+    }
+    Ok((header, tables, &slice[reader.index..]))
+}
+
+/// Check and skip the magic bytes of the given `ReadOptions`, if it has any.
+pub(crate) fn strip_magic<'s>(slice: &'s [u8], config: &ReadOptions) -> ReadResult<&'s [u8]> {
+    match config.magic {
+        Some(magic) => slice.strip_prefix(magic).ok_or(ReadError::InvalidMagic),
+        None => Ok(slice),
+    }
+}
+
+/// Write the wire format revision of the given writer and the given schema version.
+pub(crate) fn write_version_header<W: Writer + ?Sized>(
+    writer: &mut W,
+    version: &Version,
+) -> WriteResult {
+    let revision = writer.wire_revision();
+    // Revision 0 did not have a revision byte
+    if revision > 0 {
+        revision.serialize(writer)?;
+    }
+    version.serialize(writer)
+}
+
+/// Read the version header at the start of the given slice, returning the header and the remaining bytes.
+///
+/// Only documents written with a `WriteConfig` can contain domain versions and features, for other formats these are ignored.
+pub(crate) fn split_version_header(slice: &[u8]) -> ReadResult<(VersionHeader, &[u8])> {
+    split_header(slice).map(|(header, _, slice)| (header, slice))
+}
+
+/// Read the version header at the start of the given slice, returning the header, the tables in the header and the remaining bytes.
+pub(crate) fn split_header(slice: &[u8]) -> ReadResult<(VersionHeader, HeaderTables<'_>, &[u8])> {
+    let (revision, flags, slice) = match slice.split_first() {
+        // Revision 0 did not have a revision byte, its header started with the first byte of the major version
+        Some((0, _)) => (0, 0, slice),
+        Some((&byte, rest)) => {
+            let (revision, flags) = (byte & REVISION_MASK, byte & !REVISION_MASK);
+            if revision > WIRE_REVISION
+                || flags
+                    & !(FLAG_DOMAINS
+                        | FLAG_FEATURES
+                        | FLAG_VARINT
+                        | FLAG_LITTLE_ENDIAN
+                        | FLAG_COMPRESSED)
+                    != 0
+                || (flags != 0 && revision < 2)
+            {
+                return Err(ReadError::UnsupportedWireRevision(byte));
+            }
+            (revision, flags, rest)
+        }
+        None => return Err(ReadError::EndOfInput),
     };
+    let mut reader = SliceReader::new(
+        slice,
+        VersionHeader {
+            revision,
+            version: Version::new(0, 0, 0),
+        },
+    );
     let version = Version::deserialize(&mut reader)?;
-    reader.version = version;
-    let result = T::deserialize(&mut reader)?;
+    // The tables are validated here, so `SliceReader` does not have to handle errors when looking up a domain or feature
+    let mut tables = HeaderTables::default();
+    if flags & FLAG_VARINT != 0 {
+        tables.integer_encoding = IntegerEncoding::Varint;
+    }
+    if flags & FLAG_LITTLE_ENDIAN != 0 {
+        tables.byte_order = ByteOrder::LittleEndian;
+    }
+    tables.compressed = flags & FLAG_COMPRESSED != 0;
+    if flags & FLAG_DOMAINS != 0 {
+        #[cfg(feature = "std")]
+        read_domains(&mut reader, |domain, version| {
+            tables.domains.push((domain, version))
+        })?;
+        #[cfg(not(feature = "std"))]
+        {
+            let start = reader.index;
+            read_domains(&mut reader, |_, _| {})?;
+            tables.domains = &slice[start..reader.index];
+        }
+    }
+    if flags & FLAG_FEATURES != 0 {
+        let start = reader.index;
+        read_features(&mut reader, |_| {})?;
+        tables.features = &slice[start..reader.index];
+    }
+    Ok((
+        VersionHeader { revision, version },
+        tables,
+        &slice[reader.index..],
+    ))
+}
+
+/// Read a table of domain versions, calling `f` with the name and version of every domain.
+fn read_domains<'a>(
+    reader: &mut SliceReader<'a>,
+    mut f: impl FnMut(&'a [u8], Version),
+) -> ReadResult {
+    let count = read_varint(reader)?;
+    for _ in 0..count {
+        let len = read_varint(reader)? as usize;
+        let domain = reader.read_slice(len)?;
+        let version = Version::deserialize(reader)?;
+        f(domain, version);
+    }
+    Ok(())
+}
 
-    if config.error_on_trailing_bytes && reader.index != reader.slice.len() {
-        return Err(ReadError::TrailingBytes(reader.slice.len() - reader.index));
+/// Read a table of enabled features, calling `f` with the name of every feature.
+fn read_features<'a>(reader: &mut SliceReader<'a>, mut f: impl FnMut(&'a [u8])) -> ReadResult {
+    let count = read_varint(reader)?;
+    for _ in 0..count {
+        let len = read_varint(reader)? as usize;
+        f(reader.read_slice(len)?);
     }
-    Ok(result)
+    Ok(())
+}
+
+/// Deserialize an object from a slice that does not start with a version header, using the given header instead.
+pub(crate) fn deserialize_slice_without_header<'a, T: Serializable<'a>>(
+    slice: &'a [u8],
+    header: VersionHeader,
+    config: ReadOptions,
+) -> ReadResult<T> {
+    deserialize_slice_with_header(slice, header, HeaderTables::default(), config)
+}
+
+/// Deserialize an object from a slice that does not start with a header, using the header and tables that were read with `read_header`.
+pub(crate) fn deserialize_slice_with_header<'a, T: Serializable<'a>>(
+    slice: &'a [u8],
+    header: VersionHeader,
+    tables: HeaderTables<'a>,
+    config: ReadOptions,
+) -> ReadResult<T> {
+    let mut reader = SliceReader::new(slice, header);
+    reader.tables = tables;
+    reader.deserialize_remaining(config)
 }
 
 #[cfg(feature = "std")]
@@ -54,39 +631,224 @@ impl Writer for Vec<u8> {
         self.extend_from_slice(bytes);
         Ok(())
     }
+
+    fn position(&self) -> Option<usize> {
+        Some(self.len())
+    }
+
+    fn insert(&mut self, position: usize, bytes: &[u8]) -> WriteResult {
+        self.splice(position..position, bytes.iter().copied());
+        Ok(())
+    }
+}
+
+/// A writer that collects bytes in a buffer, and writes them the same way as the given writer.
+#[cfg(feature = "std")]
+pub(crate) struct BufferWriter<'w, W: ?Sized> {
+    pub buffer: Vec<u8>,
+    target: &'w W,
+}
+
+#[cfg(feature = "std")]
+impl<'w, W: Writer + ?Sized> BufferWriter<'w, W> {
+    pub fn new(target: &'w W) -> Self {
+        Self {
+            buffer: Vec::new(),
+            target,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'w, W: Writer + ?Sized> Writer for BufferWriter<'w, W> {
+    fn write(&mut self, bytes: &[u8]) -> WriteResult {
+        self.buffer.write(bytes)
+    }
+
+    fn wire_revision(&self) -> u8 {
+        self.target.wire_revision()
+    }
+
+    fn has_feature(&self, feature: &str) -> bool {
+        self.target.has_feature(feature)
+    }
+
+    fn integer_encoding(&self) -> IntegerEncoding {
+        self.target.integer_encoding()
+    }
+
+    fn byte_order(&self) -> ByteOrder {
+        self.target.byte_order()
+    }
+
+    fn canonical_nan(&self) -> bool {
+        self.target.canonical_nan()
+    }
+
+    fn position(&self) -> Option<usize> {
+        self.buffer.position()
+    }
+
+    fn insert(&mut self, position: usize, bytes: &[u8]) -> WriteResult {
+        Writer::insert(&mut self.buffer, position, bytes)
+    }
 }
 
 struct SliceReader<'a> {
-    version: Version,
+    header: VersionHeader,
+    tables: HeaderTables<'a>,
     slice: &'a [u8],
     index: usize,
 }
 
+impl<'a> SliceReader<'a> {
+    fn new(slice: &'a [u8], header: VersionHeader) -> Self {
+        Self {
+            header,
+            tables: HeaderTables::default(),
+            slice,
+            index: 0,
+        }
+    }
+
+    fn deserialize_remaining<T: Serializable<'a>>(mut self, config: ReadOptions) -> ReadResult<T> {
+        let result = match self.tables.compressed {
+            #[cfg(feature = "compression")]
+            true => crate::compression::deserialize_document(&mut self, &config)?,
+            // Without the compression feature, no codec is supported
+            #[cfg(not(feature = "compression"))]
+            true => return Err(ReadError::UnknownCodec(u8::deserialize(&mut self)?)),
+            false => T::deserialize(&mut self)?,
+        };
+
+        if config.error_on_trailing_bytes && self.index != self.slice.len() {
+            return Err(ReadError::TrailingBytes(self.slice.len() - self.index));
+        }
+        Ok(result)
+    }
+}
+
 impl<'a> Reader<'a> for SliceReader<'a> {
     fn version(&self) -> Version {
-        self.version.clone()
+        self.header.version.clone()
     }
 
-    fn read(&mut self, bytes: &mut [u8]) -> ReadResult {
-        if self.slice.len() < self.index + bytes.len() {
-            Err(ReadError::EndOfInput)
-        } else {
-            bytes.copy_from_slice(&self.slice[self.index..self.index + bytes.len()]);
-            self.index += bytes.len();
-            Ok(())
+    fn wire_revision(&self) -> u8 {
+        self.header.revision
+    }
+
+    #[cfg(feature = "std")]
+    fn domain_version(&self, domain: &str) -> Version {
+        // If a domain is in the table more than once, the last version is used
+        self.tables
+            .domains
+            .iter()
+            .rev()
+            .find(|(name, _)| *name == domain.as_bytes())
+            .map_or_else(|| self.version(), |(_, version)| version.clone())
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn domain_version(&self, domain: &str) -> Version {
+        let mut found = None;
+        if !self.tables.domains.is_empty() {
+            let mut reader = SliceReader::new(self.tables.domains, self.header.clone());
+            let _ = read_domains(&mut reader, |name, version| {
+                if name == domain.as_bytes() {
+                    found = Some(version);
+                }
+            });
         }
+        found.unwrap_or_else(|| self.version())
     }
 
-    fn read_slice(&mut self, len: usize) -> ReadResult<&'a [u8]> {
-        if self.slice.len() < self.index + len {
-            Err(ReadError::EndOfInput)
-        } else {
-            let slice = &self.slice[self.index..][..len];
-            self.index += len;
-            Ok(slice)
+    fn has_feature(&self, feature: &str) -> bool {
+        let mut found = false;
+        if !self.tables.features.is_empty() {
+            let mut reader = SliceReader::new(self.tables.features, self.header.clone());
+            let _ = read_features(&mut reader, |name| found |= name == feature.as_bytes());
         }
+        found
+    }
+
+    fn integer_encoding(&self) -> IntegerEncoding {
+        self.tables.integer_encoding
     }
+
+    fn byte_order(&self) -> ByteOrder {
+        self.tables.byte_order
+    }
+
+    fn read(&mut self, bytes: &mut [u8]) -> ReadResult {
+        bytes.copy_from_slice(self.read_slice(bytes.len())?);
+        Ok(())
+    }
+
+    fn read_slice(&mut self, len: usize) -> ReadResult<&'a [u8]> {
+        // The length can come from the data itself, so it may be larger than the slice
+        let slice = self
+            .slice
+            .get(self.index..)
+            .and_then(|remaining| remaining.get(..len))
+            .ok_or(ReadError::EndOfInput)?;
+        self.index += len;
+        Ok(slice)
+    }
+}
+/// A writer that only counts the amount of bytes written to it.
+pub(crate) struct SizeWriter<'w, W: Writer + ?Sized = dyn Writer> {
+    pub size: usize,
+    /// The writer that the data will be written to, which determines how the data is encoded.
+    target: Option<&'w W>,
 }
+
+impl<'w, W: Writer + ?Sized> SizeWriter<'w, W> {
+    /// Create a writer that counts the bytes as they would be written to `target`, or with the default settings if `target` is `None`.
+    pub fn new(target: Option<&'w W>) -> Self {
+        Self { size: 0, target }
+    }
+}
+
+impl<'w, W: Writer + ?Sized> Writer for SizeWriter<'w, W> {
+    fn write(&mut self, bytes: &[u8]) -> WriteResult {
+        self.size += bytes.len();
+        Ok(())
+    }
+
+    fn wire_revision(&self) -> u8 {
+        self.target
+            .map_or(WIRE_REVISION, |target| target.wire_revision())
+    }
+
+    fn has_feature(&self, feature: &str) -> bool {
+        self.target
+            .is_some_and(|target| target.has_feature(feature))
+    }
+
+    fn integer_encoding(&self) -> IntegerEncoding {
+        self.target
+            .map_or(IntegerEncoding::Fixed, |target| target.integer_encoding())
+    }
+
+    fn byte_order(&self) -> ByteOrder {
+        self.target
+            .map_or(ByteOrder::BigEndian, |target| target.byte_order())
+    }
+
+    fn canonical_nan(&self) -> bool {
+        self.target.is_some_and(|target| target.canonical_nan())
+    }
+
+    fn position(&self) -> Option<usize> {
+        Some(self.size)
+    }
+
+    fn insert(&mut self, _position: usize, bytes: &[u8]) -> WriteResult {
+        self.size += bytes.len();
+        Ok(())
+    }
+}
+
 pub struct SliceWriter<'a> {
     slice: &'a mut [u8],
     index: usize,
@@ -102,4 +864,19 @@ impl<'a> Writer for SliceWriter<'a> {
             Ok(())
         }
     }
+
+    fn position(&self) -> Option<usize> {
+        Some(self.index)
+    }
+
+    fn insert(&mut self, position: usize, bytes: &[u8]) -> WriteResult {
+        if self.slice.len() < self.index + bytes.len() {
+            return Err(WriteError::EndOfOutput);
+        }
+        self.slice
+            .copy_within(position..self.index, position + bytes.len());
+        self.slice[position..position + bytes.len()].copy_from_slice(bytes);
+        self.index += bytes.len();
+        Ok(())
+    }
 }
@@ -1,29 +1,222 @@
-use crate::{ReadError, ReadResult, Reader, Serializable, Version, WriteResult, Writer};
+use crate::{
+    ByteOrder, IntegerEncoding, ReadError, ReadResult, Reader, Serializable, Version, WriteError,
+    WriteResult, Writer,
+};
 #[cfg(feature = "std")]
 use alloc::{string::String, vec, vec::Vec};
+use semver::{BuildMetadata, Prerelease};
+
+/// A 64-bit FNV-1a hash of the given name, combined with the given fingerprint. This is the same hash that the derive macro uses.
+pub(crate) const fn fingerprint(name: &str, inner: u64) -> u64 {
+    fingerprint_all(name, &[inner])
+}
+
+/// A 64-bit FNV-1a hash of the given name, combined with the given fingerprints in order. This is used by `#[derive(Serializable)]`.
+#[doc(hidden)]
+pub const fn fingerprint_all(name: &str, inner: &[u64]) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325u64;
+    let bytes = name.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        hash = (hash ^ bytes[i] as u64).wrapping_mul(0x0000_0100_0000_01b3);
+        i += 1;
+    }
+    let mut j = 0;
+    while j < inner.len() {
+        let bytes = inner[j].to_be_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            hash = (hash ^ bytes[i] as u64).wrapping_mul(0x0000_0100_0000_01b3);
+            i += 1;
+        }
+        j += 1;
+    }
+    hash
+}
 
 impl<'a> Serializable<'a> for Version {
-    fn serialize(&self, writer: &mut dyn Writer) -> WriteResult {
-        (self.major as u16).serialize(writer)?;
-        (self.minor as u16).serialize(writer)?;
-        (self.patch as u16).serialize(writer)?;
-        Ok(())
+    const FINGERPRINT: u64 = fingerprint("Version", 0);
+
+    fn serialize<W: Writer + ?Sized>(&self, writer: &mut W) -> WriteResult {
+        if writer.wire_revision() < 2 {
+            // Before revision 2, versions were written as 3 `u16`s
+            let max = u16::MAX as u64;
+            if self.major > max
+                || self.minor > max
+                || self.patch > max
+                || !self.pre.is_empty()
+                || !self.build.is_empty()
+            {
+                return Err(WriteError::VersionOutOfRange(self.clone()));
+            }
+            (self.major as u16).serialize(writer)?;
+            (self.minor as u16).serialize(writer)?;
+            (self.patch as u16).serialize(writer)?;
+            return Ok(());
+        }
+        write_varint(writer, self.major)?;
+        write_varint(writer, self.minor)?;
+        write_varint(writer, self.patch)?;
+        write_identifier(writer, self.pre.as_str())?;
+        write_identifier(writer, self.build.as_str())
+    }
+    fn deserialize<R: Reader<'a> + ?Sized>(reader: &mut R) -> ReadResult<Self> {
+        if reader.wire_revision() < 2 {
+            let major = u16::deserialize(reader)?;
+            let minor = u16::deserialize(reader)?;
+            let patch = u16::deserialize(reader)?;
+            return Ok(Version::new(major as u64, minor as u64, patch as u64));
+        }
+        let mut version = Version::new(
+            read_varint(reader)?,
+            read_varint(reader)?,
+            read_varint(reader)?,
+        );
+        version.pre = read_identifier(reader, Prerelease::new)?;
+        version.build = read_identifier(reader, BuildMetadata::new)?;
+        Ok(version)
     }
-    fn deserialize(reader: &mut dyn Reader<'a>) -> ReadResult<Self> {
-        let major = u16::deserialize(reader)?;
-        let minor = u16::deserialize(reader)?;
-        let patch = u16::deserialize(reader)?;
-        Ok(Version::new(major as u64, minor as u64, patch as u64))
+}
+
+/// Write a LEB128 encoded unsigned integer.
+pub(crate) fn write_varint<W: Writer + ?Sized>(
+    writer: &mut W,
+    value: impl Into<u128>,
+) -> WriteResult {
+    let mut value = value.into();
+    let mut bytes = [0u8; 19];
+    let mut len = 0;
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes[len] = byte;
+            len += 1;
+            break;
+        }
+        bytes[len] = byte | 0x80;
+        len += 1;
     }
+    writer.write(&bytes[..len])
+}
+
+/// Read a LEB128 encoded unsigned integer.
+pub(crate) fn read_varint<'a, R: Reader<'a> + ?Sized>(reader: &mut R) -> ReadResult<u64> {
+    read_varint_bits(reader, 64).map(|value| value as u64)
+}
+
+/// Read a LEB128 encoded unsigned integer, that must fit in the given amount of bits.
+fn read_varint_bits<'a, R: Reader<'a> + ?Sized>(reader: &mut R, bits: u32) -> ReadResult<u128> {
+    let mut value = 0u128;
+    let mut shift = 0;
+    loop {
+        let byte = u8::deserialize(reader)?;
+        let remaining = bits - shift;
+        // The last byte may not continue, or contain more bits than fit in the integer
+        if remaining <= 7 && (byte & 0x80 != 0 || (byte as u32) >> remaining != 0) {
+            return Err(ReadError::InvalidVarint);
+        }
+        value |= ((byte & 0x7F) as u128) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+        shift += 7;
+    }
+}
+
+/// Conversion of an integer to the unsigned value that is written as a varint.
+trait VarintInteger: Sized {
+    /// Integers of a single byte are never written as varints.
+    const VARINT: bool = core::mem::size_of::<Self>() > 1;
+
+    fn to_varint(self) -> u128;
+    fn from_varint(value: u128) -> Self;
+}
+
+macro_rules! impl_varint_unsigned {
+    ($($ty:ty),*) => {
+        $(
+            impl VarintInteger for $ty {
+                fn to_varint(self) -> u128 {
+                    self as u128
+                }
+                fn from_varint(value: u128) -> Self {
+                    value as $ty
+                }
+            }
+        )*
+    }
+}
+
+macro_rules! impl_varint_signed {
+    ($($ty:ty => $unsigned:ty),*) => {
+        $(
+            impl VarintInteger for $ty {
+                // Zig-zag encoding, so integers close to 0 are written as small varints
+                fn to_varint(self) -> u128 {
+                    ((self << 1) ^ (self >> (<$ty>::BITS - 1))) as $unsigned as u128
+                }
+                fn from_varint(value: u128) -> Self {
+                    let value = value as $unsigned;
+                    ((value >> 1) as $ty) ^ -((value & 1) as $ty)
+                }
+            }
+        )*
+    }
+}
+
+impl_varint_unsigned! { u16, u32, u64, u128, usize }
+impl_varint_signed! { i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize }
+
+/// Write a pre-release or build metadata identifier of a version, or the name of a domain.
+pub(crate) fn write_identifier<W: Writer + ?Sized>(
+    writer: &mut W,
+    identifier: &str,
+) -> WriteResult {
+    write_varint(writer, identifier.len() as u64)?;
+    writer.write(identifier.as_bytes())
+}
+
+/// Read `len` bytes into a vec. The length is not trusted, so the vec grows as bytes are read instead of allocating it up front.
+#[cfg(feature = "std")]
+pub(crate) fn read_chunked<'a, R: Reader<'a> + ?Sized>(
+    reader: &mut R,
+    len: usize,
+) -> ReadResult<Vec<u8>> {
+    let mut bytes = Vec::new();
+    while bytes.len() < len {
+        // Grow by at most the amount that was already read, so a wrong length can not allocate much more than the input
+        let start = bytes.len();
+        bytes.resize(len.min(start + start.max(64)), 0);
+        reader.read(&mut bytes[start..])?;
+    }
+    Ok(bytes)
+}
+
+/// Read a pre-release or build metadata identifier of a version.
+fn read_identifier<'a, R: Reader<'a> + ?Sized, T>(
+    reader: &mut R,
+    parse: impl FnOnce(&str) -> Result<T, semver::Error>,
+) -> ReadResult<T> {
+    let len = read_varint(reader)? as usize;
+    #[cfg(feature = "std")]
+    let bytes = &read_chunked(reader, len)?;
+    #[cfg(not(feature = "std"))]
+    let bytes = reader.read_slice(len)?;
+
+    let identifier = core::str::from_utf8(bytes).map_err(ReadError::InvalidUtf8Str)?;
+    parse(identifier).map_err(|_| ReadError::InvalidVersion)
 }
 
 impl<'a> Serializable<'a> for &'a str {
-    fn serialize(&self, writer: &mut dyn Writer) -> WriteResult {
+    const FINGERPRINT: u64 = fingerprint("str", 0);
+
+    fn serialize<W: Writer + ?Sized>(&self, writer: &mut W) -> WriteResult {
         (self.len() as u32).serialize(writer)?;
         writer.write(self.as_bytes())?;
         Ok(())
     }
-    fn deserialize(reader: &mut dyn Reader<'a>) -> ReadResult<Self> {
+    fn deserialize<R: Reader<'a> + ?Sized>(reader: &mut R) -> ReadResult<Self> {
         let len = u32::deserialize(reader)? as usize;
         let slice = reader.read_slice(len)?;
         core::str::from_utf8(slice).map_err(ReadError::InvalidUtf8Str)
@@ -32,12 +225,14 @@ impl<'a> Serializable<'a> for &'a str {
 
 #[cfg(feature = "std")]
 impl<'a> Serializable<'a> for String {
-    fn serialize(&self, writer: &mut dyn Writer) -> WriteResult {
+    const FINGERPRINT: u64 = fingerprint("str", 0);
+
+    fn serialize<W: Writer + ?Sized>(&self, writer: &mut W) -> WriteResult {
         (self.len() as u32).serialize(writer)?;
         writer.write(self.as_bytes())?;
         Ok(())
     }
-    fn deserialize(reader: &mut dyn Reader<'a>) -> ReadResult<Self> {
+    fn deserialize<R: Reader<'a> + ?Sized>(reader: &mut R) -> ReadResult<Self> {
         let len = u32::deserialize(reader)? as usize;
         let mut blob = vec![0u8; len];
         reader.read(&mut blob[..len])?;
@@ -46,14 +241,14 @@ impl<'a> Serializable<'a> for String {
 }
 
 impl<'a> Serializable<'a> for &'a [u8] {
-    fn serialize(&self, writer: &mut dyn Writer) -> WriteResult {
+    // The same as a `Vec<u8>`
+    const FINGERPRINT: u64 = fingerprint("Vec", 0);
+
+    fn serialize<W: Writer + ?Sized>(&self, writer: &mut W) -> WriteResult {
         (self.len() as u32).serialize(writer)?;
-        for item in self.iter() {
-            item.serialize(writer)?;
-        }
-        Ok(())
+        writer.write(self)
     }
-    fn deserialize(reader: &mut dyn Reader<'a>) -> ReadResult<Self> {
+    fn deserialize<R: Reader<'a> + ?Sized>(reader: &mut R) -> ReadResult<Self> {
         let len = u32::deserialize(reader)? as usize;
         reader.read_slice(len)
     }
@@ -61,46 +256,137 @@ impl<'a> Serializable<'a> for &'a [u8] {
 
 #[cfg(feature = "std")]
 impl<'a, T: Serializable<'a>> Serializable<'a> for Vec<T> {
-    fn serialize(&self, writer: &mut dyn Writer) -> WriteResult {
+    // The fingerprint of `T` is not included: a `Vec` is the only way for a type to contain itself,
+    // and a recursive type would otherwise need its own fingerprint to compute its fingerprint.
+    const FINGERPRINT: u64 = fingerprint("Vec", 0);
+
+    fn serialize<W: Writer + ?Sized>(&self, writer: &mut W) -> WriteResult {
         (self.len() as u32).serialize(writer)?;
-        for item in self.iter() {
-            item.serialize(writer)?;
-        }
-        Ok(())
+        T::serialize_slice(self, writer)
     }
-    fn deserialize(reader: &mut dyn Reader<'a>) -> ReadResult<Self> {
+    fn deserialize<R: Reader<'a> + ?Sized>(reader: &mut R) -> ReadResult<Self> {
         let len = u32::deserialize(reader)? as usize;
-        let mut blob = Vec::with_capacity(len);
-        for _ in 0..len {
-            blob.push(T::deserialize(reader)?);
-        }
-        Ok(blob)
+        T::deserialize_vec(reader, len)
     }
 }
 
 impl<'a> Serializable<'a> for bool {
-    fn serialize(&self, writer: &mut dyn Writer) -> WriteResult {
+    const FINGERPRINT: u64 = fingerprint("bool", 0);
+    const MAX_SERIALIZED_SIZE: Option<usize> = Some(1);
+
+    fn serialize<W: Writer + ?Sized>(&self, writer: &mut W) -> WriteResult {
         (*self as u8).serialize(writer)
     }
-    fn deserialize(reader: &mut dyn Reader<'a>) -> ReadResult<Self> {
+    fn deserialize<R: Reader<'a> + ?Sized>(reader: &mut R) -> ReadResult<Self> {
         let val = u8::deserialize(reader)?;
         Ok(val == 1)
     }
 }
 
+/// The size of the stack buffer that numeric slices are converted in, before they are written or after they are read.
+const BULK_BUFFER_SIZE: usize = 1024;
+
+impl<'a> Serializable<'a> for u8 {
+    const FINGERPRINT: u64 = fingerprint("u8", 0);
+    const MAX_SERIALIZED_SIZE: Option<usize> = Some(1);
+
+    fn serialize<W: Writer + ?Sized>(&self, writer: &mut W) -> WriteResult {
+        writer.write(&[*self])
+    }
+
+    fn deserialize<R: Reader<'a> + ?Sized>(reader: &mut R) -> ReadResult<Self> {
+        let mut bytes = [0u8; 1];
+        reader.read(&mut bytes)?;
+        Ok(bytes[0])
+    }
+
+    fn serialize_slice<W: Writer + ?Sized>(items: &[Self], writer: &mut W) -> WriteResult {
+        writer.write(items)
+    }
+
+    #[cfg(feature = "std")]
+    fn deserialize_vec<R: Reader<'a> + ?Sized>(
+        reader: &mut R,
+        len: usize,
+    ) -> ReadResult<Vec<Self>> {
+        let mut items = vec![0u8; len];
+        reader.read(&mut items)?;
+        Ok(items)
+    }
+}
+
 macro_rules! impl_numeric {
     ($($ty:ty),*) => {
         $(
             impl<'a> Serializable<'a> for $ty {
-                fn serialize(&self, writer: &mut dyn Writer) -> WriteResult {
-                    let bytes = self.to_be_bytes();
+                const FINGERPRINT: u64 = fingerprint(stringify!($ty), 0);
+                const MAX_SERIALIZED_SIZE: Option<usize> = Some(core::mem::size_of::<$ty>());
+
+                fn serialize<W: Writer + ?Sized>(&self, writer: &mut W) -> WriteResult {
+                    if Self::VARINT && writer.integer_encoding() == IntegerEncoding::Varint {
+                        return write_varint(writer, self.to_varint());
+                    }
+                    let bytes = match writer.byte_order() {
+                        ByteOrder::BigEndian => self.to_be_bytes(),
+                        ByteOrder::LittleEndian => self.to_le_bytes(),
+                    };
                     writer.write(&bytes)
                 }
 
-                fn deserialize(reader: &mut dyn Reader<'a>) -> ReadResult<Self> {
+                fn deserialize<R: Reader<'a> + ?Sized>(reader: &mut R) -> ReadResult<Self> {
+                    if Self::VARINT && reader.integer_encoding() == IntegerEncoding::Varint {
+                        return read_varint_bits(reader, <$ty>::BITS).map(Self::from_varint);
+                    }
                     let mut bytes = [0u8; core::mem::size_of::<Self>()];
                     reader.read(&mut bytes[..])?;
-                    Ok(Self::from_be_bytes(bytes))
+                    Ok(match reader.byte_order() {
+                        ByteOrder::BigEndian => Self::from_be_bytes(bytes),
+                        ByteOrder::LittleEndian => Self::from_le_bytes(bytes),
+                    })
+                }
+
+                fn serialize_slice<W: Writer + ?Sized>(items: &[Self], writer: &mut W) -> WriteResult {
+                    if Self::VARINT && writer.integer_encoding() == IntegerEncoding::Varint {
+                        return items.iter().try_for_each(|item| item.serialize(writer));
+                    }
+                    const SIZE: usize = core::mem::size_of::<$ty>();
+                    let to_bytes = match writer.byte_order() {
+                        ByteOrder::BigEndian => <$ty>::to_be_bytes,
+                        ByteOrder::LittleEndian => <$ty>::to_le_bytes,
+                    };
+                    let mut buffer = [0u8; BULK_BUFFER_SIZE];
+                    for chunk in items.chunks(BULK_BUFFER_SIZE / SIZE) {
+                        for (bytes, item) in buffer.chunks_exact_mut(SIZE).zip(chunk) {
+                            bytes.copy_from_slice(&to_bytes(*item));
+                        }
+                        writer.write(&buffer[..chunk.len() * SIZE])?;
+                    }
+                    Ok(())
+                }
+
+                #[cfg(feature = "std")]
+                fn deserialize_vec<R: Reader<'a> + ?Sized>(reader: &mut R, len: usize) -> ReadResult<Vec<Self>> {
+                    if Self::VARINT && reader.integer_encoding() == IntegerEncoding::Varint {
+                        return (0..len).map(|_| Self::deserialize(reader)).collect();
+                    }
+                    const SIZE: usize = core::mem::size_of::<$ty>();
+                    let from_bytes = match reader.byte_order() {
+                        ByteOrder::BigEndian => <$ty>::from_be_bytes,
+                        ByteOrder::LittleEndian => <$ty>::from_le_bytes,
+                    };
+                    let mut items = Vec::with_capacity(len);
+                    let mut buffer = [0u8; BULK_BUFFER_SIZE];
+                    while items.len() < len {
+                        let count = (len - items.len()).min(BULK_BUFFER_SIZE / SIZE);
+                        let bytes = &mut buffer[..count * SIZE];
+                        reader.read(bytes)?;
+                        items.extend(bytes.chunks_exact(SIZE).map(|bytes| {
+                            let mut item = [0u8; SIZE];
+                            item.copy_from_slice(bytes);
+                            from_bytes(item)
+                        }));
+                    }
+                    Ok(items)
                 }
             }
         )*
@@ -108,6 +394,343 @@ macro_rules! impl_numeric {
 }
 
 impl_numeric! {
-    i8, i16, i32, i64, i128, isize,
-    u8, u16, u32, u64, u128, usize
+    i8, i16, i32, i64, i128,
+    u16, u32, u64, u128
+}
+
+macro_rules! impl_pointer_sized {
+    ($($ty:ty => $wire:ty, $legacy_32:ty;)*) => {
+        $(
+            impl<'a> Serializable<'a> for $ty {
+                const FINGERPRINT: u64 = fingerprint(stringify!($ty), 0);
+                const MAX_SERIALIZED_SIZE: Option<usize> = <$wire as Serializable>::MAX_SERIALIZED_SIZE;
+
+                fn serialize<W: Writer + ?Sized>(&self, writer: &mut W) -> WriteResult {
+                    // Before revision 3, pointer-sized integers were written with the width of the platform
+                    if writer.wire_revision() < 3 && core::mem::size_of::<$ty>() == 4 {
+                        return (*self as $legacy_32).serialize(writer);
+                    }
+                    (*self as $wire).serialize(writer)
+                }
+
+                fn deserialize<R: Reader<'a> + ?Sized>(reader: &mut R) -> ReadResult<Self> {
+                    if reader.wire_revision() < 3 && core::mem::size_of::<$ty>() == 4 {
+                        return Ok(<$legacy_32>::deserialize(reader)? as $ty);
+                    }
+                    let value = <$wire>::deserialize(reader)?;
+                    <$ty as core::convert::TryFrom<$wire>>::try_from(value).map_err(|_| ReadError::IntegerOutOfRange(value as i128))
+                }
+            }
+        )*
+    }
+}
+
+impl_pointer_sized! {
+    usize => u64, u32;
+    isize => i64, i32;
+}
+
+macro_rules! impl_float {
+    ($($ty:ty => $bits:ty, $canonical_nan:expr;)*) => {
+        $(
+            impl<'a> Serializable<'a> for $ty {
+                const FINGERPRINT: u64 = fingerprint(stringify!($ty), 0);
+                const MAX_SERIALIZED_SIZE: Option<usize> = Some(core::mem::size_of::<$ty>());
+
+                // Floats are always written with their full width, also when integers are written as varints
+                fn serialize<W: Writer + ?Sized>(&self, writer: &mut W) -> WriteResult {
+                    let bits = if self.is_nan() && writer.canonical_nan() {
+                        $canonical_nan
+                    } else {
+                        self.to_bits()
+                    };
+                    let bytes = match writer.byte_order() {
+                        ByteOrder::BigEndian => bits.to_be_bytes(),
+                        ByteOrder::LittleEndian => bits.to_le_bytes(),
+                    };
+                    writer.write(&bytes)
+                }
+
+                fn deserialize<R: Reader<'a> + ?Sized>(reader: &mut R) -> ReadResult<Self> {
+                    let mut bytes = [0u8; core::mem::size_of::<Self>()];
+                    reader.read(&mut bytes[..])?;
+                    Ok(Self::from_bits(match reader.byte_order() {
+                        ByteOrder::BigEndian => <$bits>::from_be_bytes(bytes),
+                        ByteOrder::LittleEndian => <$bits>::from_le_bytes(bytes),
+                    }))
+                }
+            }
+        )*
+    }
+}
+
+impl_float! {
+    f32 => u32, 0x7FC0_0000;
+    f64 => u64, 0x7FF8_0000_0000_0000;
+}
+
+impl<'a> Serializable<'a> for char {
+    const FINGERPRINT: u64 = fingerprint("char", 0);
+    const MAX_SERIALIZED_SIZE: Option<usize> = u32::MAX_SERIALIZED_SIZE;
+
+    fn serialize<W: Writer + ?Sized>(&self, writer: &mut W) -> WriteResult {
+        (*self as u32).serialize(writer)
+    }
+    fn deserialize<R: Reader<'a> + ?Sized>(reader: &mut R) -> ReadResult<Self> {
+        let value = u32::deserialize(reader)?;
+        char::from_u32(value).ok_or(ReadError::InvalidChar(value))
+    }
+}
+
+impl<'a> Serializable<'a> for () {
+    const FINGERPRINT: u64 = fingerprint("()", 0);
+    const MAX_SERIALIZED_SIZE: Option<usize> = Some(0);
+
+    fn serialize<W: Writer + ?Sized>(&self, _writer: &mut W) -> WriteResult {
+        Ok(())
+    }
+    fn deserialize<R: Reader<'a> + ?Sized>(_reader: &mut R) -> ReadResult<Self> {
+        Ok(())
+    }
+}
+
+impl<'a, T: Serializable<'a>> Serializable<'a> for Option<T> {
+    const FINGERPRINT: u64 = fingerprint("Option", T::FINGERPRINT);
+    const MAX_SERIALIZED_SIZE: Option<usize> = max_size_sum(&[Some(1), T::MAX_SERIALIZED_SIZE]);
+
+    fn serialize<W: Writer + ?Sized>(&self, writer: &mut W) -> WriteResult {
+        match self {
+            None => 0u8.serialize(writer),
+            Some(value) => {
+                1u8.serialize(writer)?;
+                value.serialize(writer)
+            }
+        }
+    }
+    fn deserialize<R: Reader<'a> + ?Sized>(reader: &mut R) -> ReadResult<Self> {
+        match u8::deserialize(reader)? {
+            0 => Ok(None),
+            1 => T::deserialize(reader).map(Some),
+            variant => Err(ReadError::UnknownVariant(variant as u16)),
+        }
+    }
+}
+
+/// Arrays are written without a length, because the length is part of the type.
+impl<'a, T: Serializable<'a>, const N: usize> Serializable<'a> for [T; N] {
+    const FINGERPRINT: u64 = fingerprint_all("Array", &[T::FINGERPRINT, N as u64]);
+    const MAX_SERIALIZED_SIZE: Option<usize> = match T::MAX_SERIALIZED_SIZE {
+        Some(size) => size.checked_mul(N),
+        None => None,
+    };
+
+    fn serialize<W: Writer + ?Sized>(&self, writer: &mut W) -> WriteResult {
+        T::serialize_slice(self, writer)
+    }
+    fn deserialize<R: Reader<'a> + ?Sized>(reader: &mut R) -> ReadResult<Self> {
+        // Once an item fails to deserialize, the remaining items are skipped
+        let mut error = None;
+        let items = [(); N].map(|_| match error {
+            Some(_) => None,
+            None => T::deserialize(reader).map_err(|e| error = Some(e)).ok(),
+        });
+        match error {
+            Some(e) => Err(e),
+            None => Ok(items.map(|item| item.expect("all items are deserialized"))),
+        }
+    }
+}
+
+macro_rules! impl_tuple {
+    ($(($($name:ident $index:tt),+))*) => {
+        $(
+            impl<'a, $($name: Serializable<'a>),+> Serializable<'a> for ($($name,)+) {
+                const FINGERPRINT: u64 = fingerprint_all("Tuple", &[$($name::FINGERPRINT),+]);
+                const MAX_SERIALIZED_SIZE: Option<usize> = max_size_sum(&[$($name::MAX_SERIALIZED_SIZE),+]);
+
+                fn serialize<W: Writer + ?Sized>(&self, writer: &mut W) -> WriteResult {
+                    $(self.$index.serialize(writer)?;)+
+                    Ok(())
+                }
+                fn deserialize<R: Reader<'a> + ?Sized>(reader: &mut R) -> ReadResult<Self> {
+                    Ok(($($name::deserialize(reader)?,)+))
+                }
+            }
+        )*
+    }
+}
+
+impl_tuple! {
+    (T0 0)
+    (T0 0, T1 1)
+    (T0 0, T1 1, T2 2)
+    (T0 0, T1 1, T2 2, T3 3)
+    (T0 0, T1 1, T2 2, T3 3, T4 4)
+    (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5)
+    (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6)
+    (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7)
+    (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8)
+    (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9)
+    (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10)
+    (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10, T11 11)
+}
+
+/// The sum of the given maximum sizes, or `None` if any of them is `None`. This is used by `#[derive(Serializable)]`.
+#[doc(hidden)]
+pub const fn max_size_sum(sizes: &[Option<usize>]) -> Option<usize> {
+    let mut total = 0usize;
+    let mut i = 0;
+    while i < sizes.len() {
+        total = match sizes[i] {
+            Some(size) => match total.checked_add(size) {
+                Some(total) => total,
+                None => return None,
+            },
+            None => return None,
+        };
+        i += 1;
+    }
+    Some(total)
+}
+
+/// The largest of the given maximum sizes, or `None` if any of them is `None`. This is used by `#[derive(Serializable)]`.
+#[doc(hidden)]
+pub const fn max_size_max(sizes: &[Option<usize>]) -> Option<usize> {
+    let mut max = 0;
+    let mut i = 0;
+    while i < sizes.len() {
+        match sizes[i] {
+            Some(size) if size > max => max = size,
+            Some(_) => {}
+            None => return None,
+        }
+        i += 1;
+    }
+    Some(max)
+}
+
+/// The maximum size of a field that is marked with `#[binver(varint)]`, given the maximum size with fixed-width integers. This is used by `#[derive(Serializable)]`.
+///
+/// A varint of an integer with `n` bytes takes at most `n + ceil(n / 7)` bytes, so a `u16` grows the most relative to its size, from 2 to 3 bytes.
+#[doc(hidden)]
+pub const fn max_size_varint(size: Option<usize>) -> Option<usize> {
+    match size {
+        Some(size) => Some(size + size / 2),
+        None => None,
+    }
+}
+
+/// Serialize a field that is marked with `#[binver(bytes)]`. This is encoded the same as a `Vec<u8>`.
+#[doc(hidden)]
+pub fn serialize_bytes_field<B: AsRef<[u8]> + ?Sized, W: Writer + ?Sized>(
+    bytes: &B,
+    writer: &mut W,
+) -> WriteResult {
+    let bytes = bytes.as_ref();
+    (bytes.len() as u32).serialize(writer)?;
+    writer.write(bytes)
+}
+
+/// Deserialize a field that is marked with `#[binver(bytes)]`.
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub fn deserialize_bytes_field<'a, B: From<Vec<u8>>, R: Reader<'a> + ?Sized>(
+    reader: &mut R,
+) -> ReadResult<B> {
+    Vec::<u8>::deserialize(reader).map(B::from)
+}
+
+/// Serialize a field that is marked with `#[binver(varint)]`. All integers in the field are written as varints.
+#[doc(hidden)]
+pub fn serialize_varint_field<'a, T: Serializable<'a>, W: Writer + ?Sized>(
+    t: &T,
+    writer: &mut W,
+) -> WriteResult {
+    t.serialize(&mut VarintWriter { inner: writer })
+}
+
+/// Deserialize a field that is marked with `#[binver(varint)]`.
+#[doc(hidden)]
+pub fn deserialize_varint_field<'a, T: Serializable<'a>, R: Reader<'a> + ?Sized>(
+    reader: &mut R,
+) -> ReadResult<T> {
+    T::deserialize(&mut VarintReader { inner: reader })
+}
+
+/// A writer that writes all integers as varints.
+struct VarintWriter<'w, W: ?Sized> {
+    inner: &'w mut W,
+}
+
+impl<'w, W: Writer + ?Sized> Writer for VarintWriter<'w, W> {
+    fn write(&mut self, bytes: &[u8]) -> WriteResult {
+        self.inner.write(bytes)
+    }
+
+    fn wire_revision(&self) -> u8 {
+        self.inner.wire_revision()
+    }
+
+    fn has_feature(&self, feature: &str) -> bool {
+        self.inner.has_feature(feature)
+    }
+
+    fn integer_encoding(&self) -> IntegerEncoding {
+        IntegerEncoding::Varint
+    }
+
+    fn byte_order(&self) -> ByteOrder {
+        self.inner.byte_order()
+    }
+
+    fn canonical_nan(&self) -> bool {
+        self.inner.canonical_nan()
+    }
+
+    fn position(&self) -> Option<usize> {
+        self.inner.position()
+    }
+
+    fn insert(&mut self, position: usize, bytes: &[u8]) -> WriteResult {
+        self.inner.insert(position, bytes)
+    }
+}
+
+/// A reader that reads all integers as varints.
+struct VarintReader<'r, R: ?Sized> {
+    inner: &'r mut R,
+}
+
+impl<'r, 'a, R: Reader<'a> + ?Sized> Reader<'a> for VarintReader<'r, R> {
+    fn version(&self) -> Version {
+        self.inner.version()
+    }
+
+    fn wire_revision(&self) -> u8 {
+        self.inner.wire_revision()
+    }
+
+    fn domain_version(&self, domain: &str) -> Version {
+        self.inner.domain_version(domain)
+    }
+
+    fn has_feature(&self, feature: &str) -> bool {
+        self.inner.has_feature(feature)
+    }
+
+    fn integer_encoding(&self) -> IntegerEncoding {
+        IntegerEncoding::Varint
+    }
+
+    fn byte_order(&self) -> ByteOrder {
+        self.inner.byte_order()
+    }
+
+    fn read(&mut self, bytes: &mut [u8]) -> ReadResult {
+        self.inner.read(bytes)
+    }
+
+    fn read_slice(&mut self, len: usize) -> ReadResult<&'a [u8]> {
+        self.inner.read_slice(len)
+    }
 }
@@ -34,12 +34,29 @@
 
 #[cfg(feature = "std")]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 mod config;
 mod errors;
 mod helpers;
 mod implementations;
 mod traits;
+mod versioned;
+
+#[cfg(feature = "async")]
+pub mod asynch;
+pub mod chunk;
+#[cfg(feature = "compression")]
+pub mod compression;
+#[cfg(feature = "std")]
+pub mod container;
+#[cfg(feature = "crypto")]
+pub mod crypto;
+#[cfg(feature = "std")]
+pub mod framing;
+#[cfg(feature = "std")]
+pub mod record_log;
 
 pub use binver_derive::*;
 pub use semver::Version;
@@ -50,14 +67,41 @@ pub type WriteResult<T = ()> = Result<T, WriteError>;
 pub type ReadResult<T = ()> = Result<T, ReadError>;
 
 pub use self::{
-    config::ReadConfig,
+    config::{ByteOrder, IntegerEncoding, ReadConfig, ReadOptions, WriteConfig},
     errors::{ReadError, WriteError},
-    helpers::{deserialize_slice, deserialize_slice_with_config, write_to_slice},
-    traits::{Reader, Serializable, Writer},
+    helpers::{
+        deserialize_slice, deserialize_slice_with_config, deserialize_slice_with_version,
+        deserialize_slice_with_version_and_config, encode_to_array, serialized_size,
+        serialized_size_with_config, serialized_size_without_header, sniff, sniff_with_magic,
+        write_to_slice, write_to_slice_with_config, write_to_slice_without_header, MAGIC,
+        WIRE_REVISION,
+    },
+    traits::{Reader, Serializable, SerializableOwned, Writer},
+    versioned::Versioned,
+};
+
+#[cfg(feature = "std")]
+pub use self::helpers::{
+    to_vec, to_vec_with_config, to_vec_without_header, try_to_vec, try_to_vec_with_config,
+};
+
+#[cfg(feature = "checksum")]
+pub use self::helpers::{
+    deserialize_slice_checked, deserialize_slice_checked_with_config, write_to_slice_checked,
+    write_to_slice_checked_with_config,
 };
 
+#[cfg(all(feature = "std", feature = "checksum"))]
+pub use self::helpers::{to_vec_checked, to_vec_checked_with_config};
+
 #[cfg(feature = "std")]
-pub use self::helpers::to_vec;
+#[doc(hidden)]
+pub use self::implementations::deserialize_bytes_field;
+#[doc(hidden)]
+pub use self::implementations::{
+    deserialize_varint_field, fingerprint_all, max_size_max, max_size_sum, max_size_varint,
+    serialize_bytes_field, serialize_varint_field,
+};
 
 lazy_static::lazy_static! {
     #[doc(hidden)]
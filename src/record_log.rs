@@ -0,0 +1,310 @@
+//! Append-only logs containing many records, that can be written over a long period of time by different versions of an application.
+//!
+//! Every entry in the log is a single kind byte and a big-endian `u32` length, followed by the body of the entry.
+//! A version entry contains the header of the records after it, and is only written when the header changes,
+//! so a log written by a single version contains exactly one version entry.
+//!
+//! ```rust
+//! # use binver::{record_log::{RecordLogReader, RecordLogWriter}, Serializable};
+//! #[derive(Serializable, PartialEq, Debug)]
+//! pub struct Event {
+//!     #[since(0.0.1)]
+//!     pub id: u32,
+//! }
+//!
+//! let mut writer = RecordLogWriter::new(Vec::new());
+//! writer.append(&Event { id: 1 }).unwrap();
+//! writer.append(&Event { id: 2 }).unwrap();
+//! let log = writer.into_inner();
+//!
+//! let events: Vec<Event> = RecordLogReader::new(&log[..]).collect::<Result<_, _>>().unwrap();
+//! assert_eq!(events, vec![Event { id: 1 }, Event { id: 2 }]);
+//! ```
+
+use crate::{
+    helpers::{ConfiguredWriter, VersionHeader},
+    ReadConfig, ReadError, ReadResult, Serializable, SerializableOwned, Version, WriteConfig,
+    WriteResult,
+};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::Path,
+};
+
+const KIND_VERSION: u8 = 0;
+const KIND_RECORD: u8 = 1;
+const ENTRY_HEADER_SIZE: usize = 5;
+
+/// Writes records to the end of an append-only log.
+pub struct RecordLogWriter<'c, W> {
+    writer: W,
+    config: WriteConfig<'c>,
+    /// The body of the last version entry in the log
+    last_entry: Option<Vec<u8>>,
+    buffer: Vec<u8>,
+}
+
+impl<'c, W: Write> RecordLogWriter<'c, W> {
+    /// Create a writer for a new, empty log.
+    pub fn new(writer: W) -> Self {
+        Self::resume(writer, None)
+    }
+
+    /// Create a writer that appends to an existing log, of which the last version entry is `last_version`.
+    ///
+    /// The writer must be positioned at the end of the last complete entry. See `RecordLogReader::last_version` and `RecordLogReader::valid_len`.
+    pub fn resume(writer: W, last_version: Option<Version>) -> Self {
+        let last_entry = last_version.and_then(|version| {
+            let mut entry = Vec::new();
+            crate::helpers::write_version_header(&mut entry, &version).ok()?;
+            Some(entry)
+        });
+        Self {
+            writer,
+            config: WriteConfig::default(),
+            last_entry,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Set the schema version of the records that are appended with this writer. This is `binver::VERSION` by default.
+    ///
+    /// The version is compared with the last version in the log, so only a changed version writes a new version entry.
+    pub fn with_version(mut self, version: Version) -> Self {
+        self.config.schema_version = Some(version);
+        self
+    }
+
+    /// Set the `WriteConfig` of the records that are appended with this writer. See `WriteConfig` for information on the options.
+    ///
+    /// The header of the config is written in the version entries, so only a changed header writes a new version entry.
+    /// Records are recognized by their position in the log, so `WriteConfig::magic` and `WriteConfig::type_fingerprint` are not written.
+    pub fn with_config(mut self, config: WriteConfig<'c>) -> Self {
+        self.config = WriteConfig {
+            magic: None,
+            type_fingerprint: false,
+            ..config
+        };
+        self
+    }
+
+    /// Append a single record to the log. If the header of this writer differs from the last version entry in the log, a version entry is written first.
+    pub fn append<'a, T: Serializable<'a>>(&mut self, t: &T) -> WriteResult {
+        self.buffer.clear();
+        self.buffer.extend_from_slice(&[KIND_VERSION, 0, 0, 0, 0]);
+        let mut writer = ConfiguredWriter::new(&mut self.buffer, &self.config)?;
+        crate::helpers::write_header(&mut writer, &self.config, 0, false)?;
+        let entry = &self.buffer[ENTRY_HEADER_SIZE..];
+        let new_entry = match self.last_entry.as_deref() == Some(entry) {
+            true => {
+                self.buffer.clear();
+                None
+            }
+            false => {
+                let entry = entry.to_vec();
+                finish_entry(&mut self.buffer, 0);
+                Some(entry)
+            }
+        };
+        let start = self.buffer.len();
+        self.buffer.extend_from_slice(&[KIND_RECORD, 0, 0, 0, 0]);
+        t.serialize(&mut ConfiguredWriter::new(&mut self.buffer, &self.config)?)?;
+        finish_entry(&mut self.buffer, start);
+
+        // Write the version entry and the record in one go, so a torn write never leaves a version entry without its record.
+        self.writer.write_all(&self.buffer)?;
+        if new_entry.is_some() {
+            self.last_entry = new_entry;
+        }
+        Ok(())
+    }
+
+    /// Flush the underlying writer.
+    pub fn flush(&mut self) -> WriteResult {
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Return the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl RecordLogWriter<'static, File> {
+    /// Open the log at the given path for appending, creating it if it does not exist.
+    ///
+    /// If the last record in the file was not written completely, e.g. because of a crash, it is truncated from the file.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+
+        let mut reader = RecordLogReader::<_, ()>::new(io::BufReader::new(&mut file));
+        while reader.next_entry().map_err(read_error_to_io)?.is_some() {}
+        let valid_len = reader.valid_len();
+        // If the log was written with an older wire format revision, the entry differs and a new version entry is written
+        let last_entry = match reader.last_header {
+            Some(_) => Some(reader.last_entry),
+            None => None,
+        };
+
+        file.set_len(valid_len)?;
+        file.seek(SeekFrom::Start(valid_len))?;
+        Ok(Self {
+            last_entry,
+            ..Self::resume(file, None)
+        })
+    }
+}
+
+fn finish_entry(buffer: &mut [u8], start: usize) {
+    let len = (buffer.len() - start - ENTRY_HEADER_SIZE) as u32;
+    buffer[start + 1..start + ENTRY_HEADER_SIZE].copy_from_slice(&len.to_be_bytes());
+}
+
+fn read_error_to_io(e: ReadError) -> io::Error {
+    match e {
+        ReadError::Io(e) => e,
+        e => io::Error::new(io::ErrorKind::InvalidData, alloc::format!("{:?}", e)),
+    }
+}
+
+/// Lazily reads the records of a log, as an iterator of `ReadResult<T>`.
+///
+/// If the last entry of the log was not written completely, the iterator stops before it and `is_torn` returns `true`.
+pub struct RecordLogReader<R, T> {
+    reader: R,
+    config: ReadConfig,
+    last_header: Option<VersionHeader>,
+    /// The body of the last version entry, which contains the header tables of the records after it
+    last_entry: Vec<u8>,
+    valid_len: u64,
+    torn: bool,
+    buffer: Vec<u8>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<R: Read, T> RecordLogReader<R, T> {
+    /// Create a reader for the log in the given reader.
+    pub fn new(reader: R) -> Self {
+        Self::with_config(reader, ReadConfig::default())
+    }
+
+    /// Create a reader with the given `ReadConfig`, which is applied to every record. See `ReadConfig` for information on the options.
+    pub fn with_config(reader: R, config: ReadConfig) -> Self {
+        Self {
+            reader,
+            config,
+            last_header: None,
+            last_entry: Vec::new(),
+            valid_len: 0,
+            torn: false,
+            buffer: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// The version of the last version entry that has been read.
+    pub fn last_version(&self) -> Option<&Version> {
+        self.last_header.as_ref().map(|header| &header.version)
+    }
+
+    /// The amount of bytes of complete entries that have been read.
+    pub fn valid_len(&self) -> u64 {
+        self.valid_len
+    }
+
+    /// Returns `true` if the reader encountered an entry at the end of the log that was not written completely.
+    pub fn is_torn(&self) -> bool {
+        self.torn
+    }
+
+    /// Read the next entry into `self.buffer` and return its kind, or `None` at the end of the log.
+    fn next_entry(&mut self) -> ReadResult<Option<u8>> {
+        if self.torn {
+            return Ok(None);
+        }
+        let mut header = [0u8; ENTRY_HEADER_SIZE];
+        let read = read_fully(&mut self.reader, &mut header)?;
+        if read == 0 {
+            return Ok(None);
+        }
+        if read < header.len() {
+            self.torn = true;
+            return Ok(None);
+        }
+        let mut len = [0u8; 4];
+        len.copy_from_slice(&header[1..]);
+        let len = u32::from_be_bytes(len) as usize;
+
+        self.buffer.clear();
+        (&mut self.reader)
+            .take(len as u64)
+            .read_to_end(&mut self.buffer)?;
+        if self.buffer.len() < len {
+            self.torn = true;
+            return Ok(None);
+        }
+        self.valid_len += (ENTRY_HEADER_SIZE + len) as u64;
+
+        match header[0] {
+            KIND_VERSION => {
+                let (header, _) = crate::helpers::split_version_header(&self.buffer)?;
+                self.last_header = Some(header);
+                core::mem::swap(&mut self.last_entry, &mut self.buffer);
+            }
+            KIND_RECORD => {}
+            kind => return Err(ReadError::InvalidRecordKind(kind)),
+        }
+        Ok(Some(header[0]))
+    }
+}
+
+impl<R: Read, T: SerializableOwned> Iterator for RecordLogReader<R, T> {
+    type Item = ReadResult<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.next_entry() {
+                Ok(Some(KIND_RECORD)) => break,
+                Ok(Some(_)) => {}
+                Ok(None) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        if self.last_header.is_none() {
+            return Some(Err(ReadError::MissingRecordVersion));
+        }
+        let result =
+            crate::helpers::split_header(&self.last_entry).and_then(|(header, tables, _)| {
+                crate::helpers::deserialize_slice_with_header(
+                    &self.buffer,
+                    header,
+                    tables,
+                    self.config.clone().into(),
+                )
+            });
+        Some(result)
+    }
+}
+
+/// Read until `bytes` is full or the reader is exhausted, returning the amount of bytes read.
+fn read_fully<R: Read>(reader: &mut R, bytes: &mut [u8]) -> ReadResult<usize> {
+    let mut read = 0;
+    while read < bytes.len() {
+        match reader.read(&mut bytes[read..]) {
+            Ok(0) => break,
+            Ok(n) => read += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(ReadError::Io(e)),
+        }
+    }
+    Ok(read)
+}
@@ -1,12 +1,54 @@
-use crate::{ReadResult, Version, WriteResult};
+use crate::{ByteOrder, IntegerEncoding, ReadResult, Version, WriteResult};
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
 
 /// The main trait of this crate that is used for (de)serialization
 pub trait Serializable<'a>: Sized {
+    /// A fingerprint of the schema of this type. This is written to the header when `WriteConfig::type_fingerprint` is set.
+    ///
+    /// `#[derive(Serializable)]` computes this from the name of the type, and the names, types and versions of all fields.
+    const FINGERPRINT: u64 = 0;
+
+    /// The maximum amount of bytes that `serialize` writes with fixed-width integers, or `None` if the size is not bounded, e.g. for a `Vec<T>`.
+    /// Documents written with `IntegerEncoding::Varint` can be larger.
+    ///
+    /// `#[derive(Serializable)]` computes this from the fields of the type. See `encode_to_array` for a way to use this.
+    const MAX_SERIALIZED_SIZE: Option<usize> = None;
+
     /// Serialize the current object into the given writer.
-    fn serialize(&self, writer: &mut dyn Writer) -> WriteResult;
+    ///
+    /// This is generic over the writer, so the writes can be inlined. A `&mut dyn Writer` can be passed as well.
+    fn serialize<W: Writer + ?Sized>(&self, writer: &mut W) -> WriteResult;
 
     /// Attempt to deserialize this object from the given reader.
-    fn deserialize(reader: &mut dyn Reader<'a>) -> ReadResult<Self>;
+    ///
+    /// This is generic over the reader, so the reads can be inlined. A `&mut dyn Reader` can be passed as well.
+    fn deserialize<R: Reader<'a> + ?Sized>(reader: &mut R) -> ReadResult<Self>;
+
+    /// Serialize all items of the given slice, without a length. This is used to serialize the items of a `Vec<Self>`.
+    ///
+    /// The numeric types override this to write the items in bulk, instead of one at a time.
+    fn serialize_slice<W: Writer + ?Sized>(items: &[Self], writer: &mut W) -> WriteResult {
+        for item in items {
+            item.serialize(writer)?;
+        }
+        Ok(())
+    }
+
+    /// Deserialize `len` items, that were serialized with `serialize_slice`. This is used to deserialize a `Vec<Self>`.
+    ///
+    /// The numeric types override this to read the items in bulk, instead of one at a time.
+    #[cfg(feature = "std")]
+    fn deserialize_vec<R: Reader<'a> + ?Sized>(
+        reader: &mut R,
+        len: usize,
+    ) -> ReadResult<Vec<Self>> {
+        let mut items = Vec::with_capacity(len);
+        for _ in 0..len {
+            items.push(Self::deserialize(reader)?);
+        }
+        Ok(items)
+    }
 }
 
 /// Generic writer
@@ -14,12 +56,80 @@ pub trait Writer {
     /// Write all bytes from the given slice to the writer.
     /// This function must block until all bytes have been written.
     fn write(&mut self, bytes: &[u8]) -> WriteResult;
+    /// Return the revision of the binver wire format being written. See `WIRE_REVISION` for more information.
+    ///
+    /// This is `WIRE_REVISION` by default, unless the data is written with `WriteConfig::wire_revision`.
+    fn wire_revision(&self) -> u8 {
+        crate::WIRE_REVISION
+    }
+    /// Return `true` if the given feature is enabled, so fields with a `#[binver(feature = "..")]` attribute are written.
+    ///
+    /// Features are enabled with `WriteConfig::features`. By default no features are enabled.
+    fn has_feature(&self, feature: &str) -> bool {
+        let _ = feature;
+        false
+    }
+    /// Return how integers are written. This is `IntegerEncoding::Fixed` by default, unless the data is written with `WriteConfig::integer_encoding`.
+    fn integer_encoding(&self) -> IntegerEncoding {
+        IntegerEncoding::Fixed
+    }
+    /// Return the byte order that integers are written in. This is `ByteOrder::BigEndian` by default, unless the data is written with `WriteConfig::byte_order`.
+    fn byte_order(&self) -> ByteOrder {
+        ByteOrder::BigEndian
+    }
+    /// Return `true` if all NaN floats should be written as the same canonical NaN, so equal documents are always written as the same bytes.
+    ///
+    /// This is `false` by default, unless the data is written with `WriteConfig::canonical_nan`.
+    fn canonical_nan(&self) -> bool {
+        false
+    }
+    /// Return the amount of bytes that have been written, if this writer supports `insert`. This is `None` by default.
+    ///
+    /// This lets a `Versioned` write its length after its contents, instead of serializing its contents twice.
+    fn position(&self) -> Option<usize> {
+        None
+    }
+    /// Insert the given bytes at the given position, moving the bytes that were written after it.
+    ///
+    /// This is only called with a position that was returned by `position`, so writers that return `None` don't have to implement it.
+    fn insert(&mut self, position: usize, bytes: &[u8]) -> WriteResult {
+        let _ = (position, bytes);
+        unreachable!("`Writer::insert` is only called if `Writer::position` returns `Some`")
+    }
 }
 
 /// Generic reader
 pub trait Reader<'a> {
     /// Return the version of the format being read.
     fn version(&self) -> Version;
+    /// Return the revision of the binver wire format being read. See `WIRE_REVISION` for more information.
+    ///
+    /// Readers that don't know the revision of their data should return `WIRE_REVISION`, which is the default.
+    fn wire_revision(&self) -> u8 {
+        crate::WIRE_REVISION
+    }
+    /// Return the version of the given domain, that types with a `#[binver(domain = "..")]` attribute are read with.
+    ///
+    /// If the data does not contain a version for this domain, this returns `version()`, which is the default.
+    fn domain_version(&self, domain: &str) -> Version {
+        let _ = domain;
+        self.version()
+    }
+    /// Return `true` if the data was written with the given feature enabled, so fields with a `#[binver(feature = "..")]` attribute are read.
+    ///
+    /// By default no features are enabled.
+    fn has_feature(&self, feature: &str) -> bool {
+        let _ = feature;
+        false
+    }
+    /// Return how integers are encoded in the data being read. This is `IntegerEncoding::Fixed` by default.
+    fn integer_encoding(&self) -> IntegerEncoding {
+        IntegerEncoding::Fixed
+    }
+    /// Return the byte order of the integers in the data being read. This is `ByteOrder::BigEndian` by default.
+    fn byte_order(&self) -> ByteOrder {
+        ByteOrder::BigEndian
+    }
     /// Fill the given slice with bytes. All bytes must be read.
     fn read(&mut self, bytes: &mut [u8]) -> ReadResult;
     /// Read a slice of length `len` from the reader. Must be return exactly the amount of bytes being requested.
@@ -27,3 +137,10 @@ pub trait Reader<'a> {
     /// If the reader does not have it's own internal buffer (e.g. `std::fs::File`) `ReadError::ReaderNotPersistent` should be returned.
     fn read_slice(&mut self, len: usize) -> ReadResult<&'a [u8]>;
 }
+
+/// A `Serializable` type that does not borrow from the data it is deserialized from.
+///
+/// This is needed when the input buffer does not outlive the deserialized value, e.g. when reading from a stream.
+pub trait SerializableOwned: for<'a> Serializable<'a> {}
+
+impl<T> SerializableOwned for T where T: for<'a> Serializable<'a> {}
@@ -0,0 +1,234 @@
+#[cfg(not(feature = "std"))]
+use crate::helpers::SizeWriter;
+use crate::{
+    helpers::VersionHeader, implementations::fingerprint, ByteOrder, IntegerEncoding, ReadError,
+    ReadResult, Reader, Serializable, Version, WriteError, WriteResult, Writer,
+};
+
+/// A value that is serialized with its own version, independent of the version of the document it is in.
+///
+/// This is useful for data that evolves independently of the document it is embedded in, e.g. plugin data in a save file.
+///
+/// A `Versioned<T>` is written as a `u32` length, followed by the wire format revision, the version of `T` and the serialized `T`.
+/// While `T` is deserialized, `Reader::version` returns the version of the sub-document instead of the version of the document.
+/// Any bytes of the sub-document that are not read, e.g. fields that were added in a newer version, are skipped.
+///
+/// ```rust
+/// # use binver::{Serializable, Version, Versioned};
+/// # #[cfg(feature = "std")]
+/// # {
+/// #[derive(Serializable, PartialEq, Debug, Default)]
+/// pub struct PluginData {
+///     #[since(1.0.0)]
+///     pub enabled: bool,
+/// }
+///
+/// #[derive(Serializable, PartialEq, Debug)]
+/// pub struct SaveFile {
+///     #[since(0.0.1)]
+///     pub plugin: Versioned<PluginData>,
+/// }
+///
+/// let save = SaveFile {
+///     plugin: Versioned::new(Version::new(1, 2, 0), PluginData { enabled: true }),
+/// };
+/// let serialized = binver::to_vec(&save);
+/// let deserialized: SaveFile = binver::deserialize_slice(&serialized).unwrap();
+/// assert_eq!(deserialized, save);
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Versioned<T> {
+    /// The version of the schema of `value`. This is written in the sub-document header.
+    pub version: Version,
+    /// The value of the sub-document.
+    pub value: T,
+}
+
+impl<T> Versioned<T> {
+    /// Create a new sub-document of the given version.
+    pub fn new(version: Version, value: T) -> Self {
+        Self { version, value }
+    }
+
+    /// Return the value of the sub-document.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: Default> Default for Versioned<T> {
+    /// A sub-document with version `0.0.0` and the default value. This is used when the sub-document did not exist in an older version.
+    fn default() -> Self {
+        Self::new(Version::new(0, 0, 0), T::default())
+    }
+}
+
+impl<'a, T: Serializable<'a>> Serializable<'a> for Versioned<T> {
+    const FINGERPRINT: u64 = fingerprint("Versioned", T::FINGERPRINT);
+
+    fn serialize<W: Writer + ?Sized>(&self, writer: &mut W) -> WriteResult {
+        if let Some(start) = writer.position() {
+            // The length is inserted before the sub-document once it is known
+            self.write_contents(writer)?;
+            let len = writer.position().unwrap_or(start) - start;
+            let mut length = LengthWriter {
+                bytes: [0; 8],
+                len: 0,
+                target: &*writer,
+            };
+            write_length(&mut length, len)?;
+            return writer.insert(start, &length.bytes[..length.len]);
+        }
+
+        #[cfg(feature = "std")]
+        {
+            let mut buffer = crate::helpers::BufferWriter::new(&*writer);
+            self.write_contents(&mut buffer)?;
+            let buffer = buffer.buffer;
+            write_length(writer, buffer.len())?;
+            writer.write(&buffer)
+        }
+        // Without an allocator the value is serialized twice, first to get the length of the sub-document
+        #[cfg(not(feature = "std"))]
+        {
+            let mut size = SizeWriter::new(Some(&*writer));
+            self.write_contents(&mut size)?;
+            write_length(writer, size.size)?;
+            self.write_contents(writer)
+        }
+    }
+
+    fn deserialize<R: Reader<'a> + ?Sized>(reader: &mut R) -> ReadResult<Self> {
+        let len = u32::deserialize(reader)? as usize;
+        let revision = u8::deserialize(reader)?;
+        if revision > crate::WIRE_REVISION {
+            return Err(ReadError::UnsupportedWireRevision(revision));
+        }
+        let mut reader = SubReader {
+            inner: reader,
+            header: VersionHeader {
+                revision,
+                version: Version::new(0, 0, 0),
+            },
+            remaining: len.checked_sub(1).ok_or(ReadError::EndOfInput)?,
+        };
+        reader.header.version = Version::deserialize(&mut reader)?;
+        let version = reader.header.version.clone();
+        let value = T::deserialize(&mut reader)?;
+        reader.skip_remaining()?;
+        Ok(Self { version, value })
+    }
+}
+
+/// Write the length of a sub-document, which must fit in a `u32`.
+fn write_length<W: Writer + ?Sized>(writer: &mut W, len: usize) -> WriteResult {
+    if len > u32::MAX as usize {
+        return Err(WriteError::FrameTooLarge(len));
+    }
+    (len as u32).serialize(writer)
+}
+
+/// A writer for the length of a sub-document, that encodes it the same way as the writer the sub-document is in.
+struct LengthWriter<'w, W: ?Sized> {
+    bytes: [u8; 8],
+    len: usize,
+    target: &'w W,
+}
+
+impl<'w, W: Writer + ?Sized> Writer for LengthWriter<'w, W> {
+    fn write(&mut self, bytes: &[u8]) -> WriteResult {
+        self.bytes
+            .get_mut(self.len..self.len + bytes.len())
+            .ok_or(WriteError::EndOfOutput)?
+            .copy_from_slice(bytes);
+        self.len += bytes.len();
+        Ok(())
+    }
+
+    fn wire_revision(&self) -> u8 {
+        self.target.wire_revision()
+    }
+
+    fn integer_encoding(&self) -> IntegerEncoding {
+        self.target.integer_encoding()
+    }
+
+    fn byte_order(&self) -> ByteOrder {
+        self.target.byte_order()
+    }
+}
+
+impl<T> Versioned<T> {
+    fn write_contents<'a, W: Writer + ?Sized>(&self, writer: &mut W) -> WriteResult
+    where
+        T: Serializable<'a>,
+    {
+        writer.wire_revision().serialize(writer)?;
+        self.version.serialize(writer)?;
+        self.value.serialize(writer)
+    }
+}
+
+/// A reader over the contents of a sub-document, that returns the version of the sub-document.
+struct SubReader<'r, R: ?Sized> {
+    inner: &'r mut R,
+    header: VersionHeader,
+    remaining: usize,
+}
+
+impl<'r, 'a, R: Reader<'a> + ?Sized> SubReader<'r, R> {
+    fn take(&mut self, len: usize) -> ReadResult {
+        self.remaining = self
+            .remaining
+            .checked_sub(len)
+            .ok_or(ReadError::EndOfInput)?;
+        Ok(())
+    }
+
+    fn skip_remaining(&mut self) -> ReadResult {
+        let mut buffer = [0u8; 64];
+        while self.remaining > 0 {
+            let len = self.remaining.min(buffer.len());
+            self.read(&mut buffer[..len])?;
+        }
+        Ok(())
+    }
+}
+
+impl<'r, 'a, R: Reader<'a> + ?Sized> Reader<'a> for SubReader<'r, R> {
+    fn version(&self) -> Version {
+        self.header.version.clone()
+    }
+
+    fn wire_revision(&self) -> u8 {
+        self.header.revision
+    }
+
+    fn domain_version(&self, _domain: &str) -> Version {
+        // All types in a sub-document are read with the version of the sub-document
+        self.header.version.clone()
+    }
+
+    fn has_feature(&self, feature: &str) -> bool {
+        self.inner.has_feature(feature)
+    }
+
+    fn integer_encoding(&self) -> IntegerEncoding {
+        self.inner.integer_encoding()
+    }
+
+    fn byte_order(&self) -> ByteOrder {
+        self.inner.byte_order()
+    }
+
+    fn read(&mut self, bytes: &mut [u8]) -> ReadResult {
+        self.take(bytes.len())?;
+        self.inner.read(bytes)
+    }
+
+    fn read_slice(&mut self, len: usize) -> ReadResult<&'a [u8]> {
+        self.take(len)?;
+        self.inner.read_slice(len)
+    }
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "async")]
+
+use binver::{ReadConfig, ReadError, Serializable, Version, WriteConfig};
+use futures::{executor::block_on, io::Cursor};
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct Test {
+    #[since(0.0.1)]
+    pub id: u32,
+    #[since(0.0.1)]
+    pub name: String,
+}
+
+#[test]
+fn test_async_roundtrip() {
+    let test = Test {
+        id: 5,
+        name: String::from("Trangar"),
+    };
+
+    let mut writer = Cursor::new(Vec::new());
+    block_on(binver::asynch::to_async_writer(&mut writer, &test)).unwrap();
+    let bytes = writer.into_inner();
+    assert_eq!(bytes, binver::to_vec(&test));
+
+    let mut reader = Cursor::new(bytes);
+    let deserialized: Test = block_on(binver::asynch::from_async_reader(&mut reader)).unwrap();
+    assert_eq!(test, deserialized);
+}
+
+#[test]
+fn test_async_trailing_bytes() {
+    let config = ReadConfig {
+        error_on_trailing_bytes: true,
+    };
+    let mut bytes = binver::to_vec(&Test {
+        id: 5,
+        name: String::new(),
+    });
+    bytes.push(0);
+
+    let mut reader = Cursor::new(bytes);
+    let result: Result<Test, _> = block_on(binver::asynch::from_async_reader_with_config(
+        &mut reader,
+        config,
+    ));
+    assert!(matches!(result, Err(ReadError::TrailingBytes(1))));
+}
+
+#[test]
+fn test_async_with_config() {
+    let config = WriteConfig {
+        schema_version: Some(Version::new(0, 0, 2)),
+        ..WriteConfig::default()
+    };
+    let test = Test {
+        id: 5,
+        name: String::from("Trangar"),
+    };
+
+    let mut writer = Cursor::new(Vec::new());
+    block_on(binver::asynch::to_async_writer_with_config(
+        &mut writer,
+        &test,
+        config.clone(),
+    ))
+    .unwrap();
+    let bytes = writer.into_inner();
+    assert_eq!(bytes, binver::to_vec_with_config(&test, config));
+    assert_eq!(
+        binver::sniff_with_magic(&bytes, &[]),
+        Some(Version::new(0, 0, 2))
+    );
+}
@@ -0,0 +1,120 @@
+use binver::{ReadError, Serializable, Version};
+
+#[derive(Serializable, Debug, PartialEq, Default)]
+pub struct Tile<'a> {
+    #[since(0.0.1)]
+    pub label: Option<&'a str>,
+    #[since(0.0.1)]
+    pub position: (u8, i16),
+    #[since(0.0.1)]
+    pub neighbours: [u16; 3],
+    #[since(0.0.1)]
+    pub glyph: char,
+    #[since(0.0.1)]
+    pub marker: (),
+    #[since(0.0.1)]
+    pub owner: Option<u32>,
+}
+
+const TILE: Tile = Tile {
+    label: Some("a"),
+    position: (1, -1),
+    neighbours: [2, 3, 4],
+    glyph: 'é',
+    marker: (),
+    owner: None,
+};
+
+#[test]
+fn test_builtin_types() {
+    let mut slice = [0u8; 64];
+    let length = binver::write_to_slice(&mut slice, &TILE).unwrap();
+    assert_eq!(
+        &slice[6..length],
+        &[
+            1, 0, 0, 0, 1, b'a', // label
+            1, 0xFF, 0xFF, // position
+            0, 2, 0, 3, 0, 4, // neighbours, without a length
+            0, 0, 0, 0xE9, // glyph
+            0,    // owner
+        ]
+    );
+    let deserialized: Tile = binver::deserialize_slice(&slice[..length]).unwrap();
+    assert_eq!(deserialized, TILE);
+}
+
+#[test]
+fn test_invalid_char() {
+    let mut data = [3, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+    data[6..].copy_from_slice(&0xD800u32.to_be_bytes());
+    assert!(matches!(
+        binver::deserialize_slice::<char>(&data),
+        Err(ReadError::InvalidChar(0xD800))
+    ));
+}
+
+#[test]
+fn test_invalid_option() {
+    let data = [3, 0, 0, 1, 0, 0, 2];
+    assert!(matches!(
+        binver::deserialize_slice::<Option<u8>>(&data),
+        Err(ReadError::UnknownVariant(2))
+    ));
+}
+
+#[test]
+fn test_array_error() {
+    // The second item is not a valid char
+    let mut data = [0u8; 14];
+    data[..6].copy_from_slice(&[3, 0, 0, 1, 0, 0]);
+    data[6..10].copy_from_slice(&('x' as u32).to_be_bytes());
+    data[10..].copy_from_slice(&0x11_0000u32.to_be_bytes());
+    assert!(matches!(
+        binver::deserialize_slice::<[char; 2]>(&data),
+        Err(ReadError::InvalidChar(0x11_0000))
+    ));
+    assert!(matches!(
+        binver::deserialize_slice::<[char; 3]>(&data),
+        Err(ReadError::InvalidChar(0x11_0000))
+    ));
+}
+
+type Nested = (Option<[(u8, char); 2]>, [[u8; 2]; 2]);
+
+#[test]
+fn test_nested() {
+    let value: Nested = (Some([(1, 'x'), (2, 'y')]), [[1, 2], [3, 4]]);
+    let mut slice = [0u8; 64];
+    let length = binver::write_to_slice(&mut slice, &value).unwrap();
+    assert_eq!(length, 6 + 1 + 2 * 5 + 4);
+    let deserialized: Nested = binver::deserialize_slice(&slice[..length]).unwrap();
+    assert_eq!(deserialized, value);
+}
+
+#[test]
+fn test_max_serialized_size() {
+    assert_eq!(<()>::MAX_SERIALIZED_SIZE, Some(0));
+    assert_eq!(char::MAX_SERIALIZED_SIZE, u32::MAX_SERIALIZED_SIZE);
+    assert_eq!(Option::<u8>::MAX_SERIALIZED_SIZE, Some(2));
+    assert_eq!(<(u8, bool)>::MAX_SERIALIZED_SIZE, Some(2));
+    assert_eq!(<[u8; 16]>::MAX_SERIALIZED_SIZE, Some(16));
+    assert_eq!(<[&str; 2]>::MAX_SERIALIZED_SIZE, None);
+    assert_eq!(Tile::MAX_SERIALIZED_SIZE, None);
+}
+
+#[test]
+fn test_fingerprints() {
+    assert_ne!(<[u8; 3]>::FINGERPRINT, <[u8; 4]>::FINGERPRINT);
+    assert_ne!(<(u8, u16)>::FINGERPRINT, <(u16, u8)>::FINGERPRINT);
+    assert_ne!(Option::<u8>::FINGERPRINT, u8::FINGERPRINT);
+}
+
+#[test]
+fn test_encode_to_array() {
+    let value = (Some(7u8), [1u8, 2, 3]);
+    let bytes: [u8; 5] = binver::encode_to_array(&value).unwrap();
+    assert_eq!(bytes, [1, 7, 1, 2, 3]);
+    let deserialized: (Option<u8>, [u8; 3]) =
+        binver::deserialize_slice_with_version(&bytes, Version::new(0, 0, 1)).unwrap();
+    assert_eq!(deserialized, value);
+}
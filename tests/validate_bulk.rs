@@ -0,0 +1,74 @@
+#![cfg(feature = "std")]
+
+use binver::{ReadConfig, Serializable};
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct Texture {
+    #[since(0.0.1)]
+    pub pixels: Vec<u32>,
+    #[since(0.0.1)]
+    pub samples: Vec<i16>,
+    #[since(0.0.1)]
+    #[binver(bytes)]
+    pub raw: Box<[u8]>,
+}
+
+#[test]
+fn test_numeric_vec_encoding() {
+    // Numeric vectors are written in bulk, but must be encoded the same as items written one at a time
+    let pixels: Vec<u32> = (0..5000u32).map(|i| i.wrapping_mul(0x0102_0304)).collect();
+    let serialized = binver::to_vec_without_header(&pixels).unwrap();
+    let mut expected = (pixels.len() as u32).to_be_bytes().to_vec();
+    for pixel in &pixels {
+        expected.extend_from_slice(&pixel.to_be_bytes());
+    }
+    assert_eq!(serialized, expected);
+
+    let deserialized: Vec<u32> =
+        binver::deserialize_slice_with_version(&serialized, binver::VERSION.clone()).unwrap();
+    assert_eq!(deserialized, pixels);
+}
+
+#[test]
+fn test_large_blobs() {
+    let texture = Texture {
+        pixels: (0..1 << 20).collect(),
+        samples: (0..1 << 16).map(|i| i as i16).collect(),
+        raw: vec![7u8; 4 << 20].into_boxed_slice(),
+    };
+    let serialized = binver::to_vec(&texture);
+    assert_eq!(
+        serialized.len(),
+        6 + 4 + (4 << 20) + 4 + (2 << 16) + 4 + (4 << 20)
+    );
+
+    let config = ReadConfig {
+        error_on_trailing_bytes: true,
+    };
+    let deserialized: Texture = binver::deserialize_slice_with_config(&serialized, config).unwrap();
+    assert_eq!(deserialized, texture);
+}
+
+#[test]
+fn test_bytes_field_matches_vec() {
+    let texture = Texture {
+        pixels: Vec::new(),
+        samples: Vec::new(),
+        raw: Box::new([1, 2, 3]),
+    };
+    let serialized = binver::to_vec(&texture);
+    assert_eq!(
+        &serialized[14..],
+        &binver::to_vec_without_header(&vec![1u8, 2, 3]).unwrap()[..]
+    );
+}
+
+#[test]
+fn test_truncated_vec() {
+    let serialized = binver::to_vec_without_header(&vec![1u64, 2, 3]).unwrap();
+    assert!(binver::deserialize_slice_with_version::<Vec<u64>>(
+        &serialized[..serialized.len() - 1],
+        binver::VERSION.clone()
+    )
+    .is_err());
+}
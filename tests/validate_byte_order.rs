@@ -0,0 +1,89 @@
+use binver::{ByteOrder, IntegerEncoding, Serializable, Version, WriteConfig, WriteError};
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct Vertex<'a> {
+    #[since(0.0.1)]
+    pub index: u32,
+    #[since(0.0.1)]
+    pub offset: i16,
+    #[since(0.0.1)]
+    pub name: &'a str,
+}
+
+const VERTEX: Vertex = Vertex {
+    index: 0x0102_0304,
+    offset: -2,
+    name: "v",
+};
+
+fn little_endian() -> WriteConfig<'static> {
+    WriteConfig {
+        schema_version: Some(Version::new(0, 0, 1)),
+        byte_order: ByteOrder::LittleEndian,
+        ..WriteConfig::default()
+    }
+}
+
+#[test]
+fn test_little_endian() {
+    let mut slice = [0u8; 64];
+    let length = binver::write_to_slice_with_config(&mut slice, &VERTEX, little_endian()).unwrap();
+    assert_eq!(
+        &slice[..length],
+        &[
+            0x13, 0, 0, 1, 0, 0, // revision with the little-endian flag, version 0.0.1
+            4, 3, 2, 1, // index
+            0xFE, 0xFF, // offset
+            1, 0, 0, 0, b'v', // name
+        ]
+    );
+    let deserialized: Vertex = binver::deserialize_slice(&slice[..length]).unwrap();
+    assert_eq!(deserialized, VERTEX);
+}
+
+#[test]
+fn test_big_endian_is_default() {
+    let mut slice = [0u8; 64];
+    let length = binver::write_to_slice(&mut slice, &VERTEX).unwrap();
+    assert_eq!(&slice[6..10], &[1, 2, 3, 4]);
+    let deserialized: Vertex = binver::deserialize_slice(&slice[..length]).unwrap();
+    assert_eq!(deserialized, VERTEX);
+}
+
+#[test]
+fn test_little_endian_varint() {
+    // Varints have no byte order, but both flags are recorded
+    let config = WriteConfig {
+        integer_encoding: IntegerEncoding::Varint,
+        ..little_endian()
+    };
+    let mut slice = [0u8; 64];
+    let length = binver::write_to_slice_with_config(&mut slice, &VERTEX, config).unwrap();
+    assert_eq!(slice[0], 0x33);
+    let deserialized: Vertex = binver::deserialize_slice(&slice[..length]).unwrap();
+    assert_eq!(deserialized, VERTEX);
+}
+
+#[test]
+fn test_little_endian_requires_revision_2() {
+    let config = WriteConfig {
+        wire_revision: Some(1),
+        ..little_endian()
+    };
+    let mut slice = [0u8; 64];
+    assert!(matches!(
+        binver::write_to_slice_with_config(&mut slice, &VERTEX, config),
+        Err(WriteError::UnsupportedWireRevision(1))
+    ));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_little_endian_bulk() {
+    let positions: Vec<u16> = (0..2000).collect();
+    let serialized = binver::to_vec_with_config(&positions, little_endian());
+    assert_eq!(&serialized[6..10], &2000u32.to_le_bytes());
+    assert_eq!(&serialized[10..14], &[0, 0, 1, 0]);
+    let deserialized: Vec<u16> = binver::deserialize_slice(&serialized).unwrap();
+    assert_eq!(deserialized, positions);
+}
@@ -0,0 +1,118 @@
+#![cfg(feature = "checksum")]
+
+use binver::{ReadError, Serializable};
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct Save<'a> {
+    #[since(0.0.1)]
+    pub level: u32,
+    #[since(0.0.1)]
+    pub name: &'a str,
+}
+
+#[test]
+fn test_checksum_roundtrip() {
+    let save = Save {
+        level: 3,
+        name: "Trangar",
+    };
+    let mut slice = [0u8; 64];
+    let length = binver::write_to_slice_checked(&mut slice, &save).unwrap();
+    assert_eq!(length, 6 + 4 + 4 + 7 + 4);
+
+    let result: Save = binver::deserialize_slice_checked(&slice[..length]).unwrap();
+    assert_eq!(result, save);
+}
+
+#[test]
+fn test_checksum_corrupted() {
+    let save = Save {
+        level: 3,
+        name: "Trangar",
+    };
+    let mut slice = [0u8; 64];
+    let length = binver::write_to_slice_checked(&mut slice, &save).unwrap();
+
+    slice[12] ^= 0xFF;
+    assert!(matches!(
+        binver::deserialize_slice_checked::<Save>(&slice[..length]),
+        Err(ReadError::ChecksumMismatch { .. })
+    ));
+
+    slice[12] ^= 0xFF;
+    assert!(matches!(
+        binver::deserialize_slice_checked::<Save>(&slice[..length - 5]),
+        Err(ReadError::ChecksumMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_checksum_with_config() {
+    use binver::{ByteOrder, IntegerEncoding, WriteConfig};
+
+    let config = WriteConfig {
+        integer_encoding: IntegerEncoding::Varint,
+        byte_order: ByteOrder::LittleEndian,
+        ..WriteConfig::default()
+    };
+    let save = Save {
+        level: 3,
+        name: "Trangar",
+    };
+    let mut slice = [0u8; 64];
+    let length =
+        binver::write_to_slice_checked_with_config(&mut slice, &save, config.clone()).unwrap();
+    let mut unchecked = [0u8; 64];
+    let unchecked_length =
+        binver::write_to_slice_with_config(&mut unchecked, &save, config).unwrap();
+    assert_eq!(length, unchecked_length + 4);
+    assert_eq!(&slice[..unchecked_length], &unchecked[..unchecked_length]);
+
+    let result: Save = binver::deserialize_slice_checked(&slice[..length]).unwrap();
+    assert_eq!(result, save);
+
+    assert!(matches!(
+        binver::write_to_slice_checked_with_config(
+            &mut slice[..unchecked_length + 2],
+            &save,
+            WriteConfig::default()
+        ),
+        Err(binver::WriteError::EndOfOutput)
+    ));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_std_checksum_roundtrip() {
+    let save = Save {
+        level: 3,
+        name: "Trangar",
+    };
+    let serialized = binver::to_vec_checked(&save).unwrap();
+    let unchecked = binver::to_vec(&save);
+    assert_eq!(&serialized[..unchecked.len()], &unchecked[..]);
+    assert_eq!(
+        &serialized[unchecked.len()..],
+        &crc32fast_reference(&unchecked).to_be_bytes()
+    );
+
+    let result: Save = binver::deserialize_slice_checked(&serialized).unwrap();
+    assert_eq!(result, save);
+}
+
+#[cfg(feature = "std")]
+fn crc32fast_reference(bytes: &[u8]) -> u32 {
+    // Bitwise CRC32 (IEEE), to make sure the wire format does not depend on the implementation
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
@@ -0,0 +1,150 @@
+use binver::{
+    chunk::{ChunkId, ChunkReader},
+    ReadError, Serializable,
+};
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct Header<'a> {
+    #[since(0.0.1)]
+    pub name: &'a str,
+    #[since(0.0.1)]
+    pub seed: u64,
+}
+
+const HEADER: ChunkId = *b"HEAD";
+
+fn document() -> Vec<u8> {
+    let mut document = Vec::new();
+    // A chunk written by a plugin this reader does not know about
+    document.extend_from_slice(b"PLGN");
+    document.extend_from_slice(&9u32.to_be_bytes());
+    document.extend_from_slice(&[0, 0, 0, 0, 0, 1, 1, 2, 3]);
+    // The header chunk
+    document.extend_from_slice(b"HEAD");
+    document.extend_from_slice(&(6u32 + 4 + 3 + 8).to_be_bytes());
+    document.extend_from_slice(&[0, 0, 0, 0, 0, 2]);
+    document.extend_from_slice(&3u32.to_be_bytes());
+    document.extend_from_slice(b"foo");
+    document.extend_from_slice(&42u64.to_be_bytes());
+    document
+}
+
+#[test]
+fn test_chunk_skip_unknown() {
+    let document = document();
+    let chunks: Vec<_> = ChunkReader::new(&document)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(&chunks[0].id, b"PLGN");
+    assert_eq!(chunks[0].body, &[1, 2, 3]);
+
+    let header = ChunkReader::new(&document)
+        .find_chunk(HEADER)
+        .unwrap()
+        .unwrap();
+    assert_eq!(header.version, binver::Version::new(0, 0, 2));
+    assert_eq!(
+        header.decode::<Header>().unwrap(),
+        Header {
+            name: "foo",
+            seed: 42
+        }
+    );
+}
+
+#[test]
+fn test_chunk_truncated() {
+    let document = document();
+    let mut reader = ChunkReader::new(&document[..document.len() - 1]);
+    assert!(reader.next().unwrap().is_ok());
+    assert!(matches!(reader.next(), Some(Err(ReadError::EndOfInput))));
+    assert!(reader.next().is_none());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_chunk_write() {
+    let mut document = Vec::new();
+    binver::chunk::write_chunk(
+        &mut document,
+        HEADER,
+        &Header {
+            name: "bar",
+            seed: 1,
+        },
+    )
+    .unwrap();
+    binver::chunk::write_chunk(&mut document, *b"LIST", &vec![1u8, 2, 3]).unwrap();
+
+    let mut reader = ChunkReader::new(&document);
+    let header = reader.next().unwrap().unwrap();
+    assert_eq!(header.version, *binver::VERSION);
+    assert_eq!(
+        header.decode::<Header>().unwrap(),
+        Header {
+            name: "bar",
+            seed: 1
+        }
+    );
+    let list = reader.next().unwrap().unwrap();
+    assert_eq!(list.decode::<Vec<u8>>().unwrap(), vec![1, 2, 3]);
+}
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct Settings {
+    #[since(0.0.1)]
+    pub volume: u8,
+    #[since(0.0.2)]
+    #[binver(feature = "hdr")]
+    pub hdr: bool,
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_chunk_with_config() {
+    use binver::{Version, WriteConfig};
+
+    let config = WriteConfig {
+        magic: Some(binver::MAGIC),
+        schema_version: Some(Version::new(0, 0, 2)),
+        features: &["hdr"],
+        ..WriteConfig::default()
+    };
+    let settings = Settings {
+        volume: 7,
+        hdr: true,
+    };
+    let mut document = Vec::new();
+    binver::chunk::write_chunk_with_config(&mut document, *b"SETT", &settings, config).unwrap();
+
+    let chunk = ChunkReader::new(&document)
+        .find_chunk(*b"SETT")
+        .unwrap()
+        .unwrap();
+    assert_eq!(chunk.version, Version::new(0, 0, 2));
+    assert_eq!(chunk.decode::<Settings>().unwrap(), settings);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_chunk_length_ignores_config() {
+    use binver::{ByteOrder, IntegerEncoding, WriteConfig};
+
+    let config = WriteConfig {
+        integer_encoding: IntegerEncoding::Varint,
+        byte_order: ByteOrder::LittleEndian,
+        ..WriteConfig::default()
+    };
+    let mut document = Vec::new();
+    binver::chunk::write_chunk_with_config(&mut document, *b"SEED", &42u64, config).unwrap();
+    binver::chunk::write_chunk(&mut document, *b"NAME", &String::from("overworld")).unwrap();
+
+    let len = u32::from_be_bytes([document[4], document[5], document[6], document[7]]) as usize;
+    assert_eq!(&document[8 + len..12 + len], b"NAME");
+    let mut reader = ChunkReader::new(&document);
+    let seed = reader.find_chunk(*b"SEED").unwrap().unwrap();
+    assert_eq!(seed.decode::<u64>().unwrap(), 42);
+    let name = reader.find_chunk(*b"NAME").unwrap().unwrap();
+    assert_eq!(name.decode::<String>().unwrap(), "overworld");
+}
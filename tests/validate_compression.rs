@@ -0,0 +1,247 @@
+#![cfg(feature = "compression")]
+
+use binver::{
+    compression::{self, Codec},
+    ReadConfig, ReadError, Serializable, Version, WriteConfig,
+};
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct World {
+    #[since(0.0.1)]
+    pub id: u32,
+    #[since(0.0.1)]
+    #[binver(compress)]
+    pub names: Vec<String>,
+}
+
+#[derive(Serializable, Debug, PartialEq)]
+pub enum Region {
+    #[since(0.0.1)]
+    Empty,
+    #[since(0.0.1)]
+    Tiles {
+        #[since(0.0.1)]
+        #[binver(compress)]
+        tiles: Vec<u16>,
+        #[since(0.0.1)]
+        name: String,
+    },
+}
+
+fn world() -> World {
+    World {
+        id: 5,
+        names: vec![String::from("Trangar"); 100],
+    }
+}
+
+#[test]
+fn test_compressed_field() {
+    let world = world();
+    let serialized = binver::to_vec(&world);
+    assert!(serialized.len() < 100);
+    assert_eq!(&serialized[6..10], &5u32.to_be_bytes());
+    assert_eq!(serialized[10], Codec::Deflate as u8);
+    assert_eq!(&serialized[11..15], &(4u32 + 100 * 11).to_be_bytes());
+
+    let deserialized: World = binver::deserialize_slice(&serialized).unwrap();
+    assert_eq!(world, deserialized);
+
+    let region = Region::Tiles {
+        tiles: vec![7; 1000],
+        name: String::from("forest"),
+    };
+    let serialized = binver::to_vec(&region);
+    let deserialized: Region = binver::deserialize_slice(&serialized).unwrap();
+    assert_eq!(region, deserialized);
+}
+
+#[test]
+fn test_compressed_document() {
+    let world = world();
+    for codec in [Codec::None, Codec::Deflate] {
+        let serialized = compression::to_vec_compressed(&world, codec);
+        // The header marks the document as compressed
+        assert_eq!(serialized[0], 0x08 | binver::WIRE_REVISION);
+        assert_eq!(serialized[6], codec as u8);
+
+        let config = ReadConfig {
+            error_on_trailing_bytes: true,
+        };
+        let deserialized: World =
+            compression::deserialize_slice_compressed_with_config(&serialized, config).unwrap();
+        assert_eq!(world, deserialized);
+    }
+}
+
+#[test]
+fn test_compressed_document_is_detected() {
+    let world = world();
+    let serialized = compression::to_vec_compressed(&world, Codec::Deflate);
+    let deserialized: World = binver::deserialize_slice(&serialized).unwrap();
+    assert_eq!(world, deserialized);
+
+    // The decompressed data can not be borrowed from
+    let serialized = compression::to_vec_compressed(&"Trangar", Codec::Deflate);
+    assert!(matches!(
+        binver::deserialize_slice::<&str>(&serialized),
+        Err(ReadError::ReaderNotPersistent)
+    ));
+    assert_eq!(
+        binver::deserialize_slice::<String>(&serialized).unwrap(),
+        "Trangar"
+    );
+}
+
+#[test]
+fn test_uncompressed_document() {
+    let serialized = binver::to_vec(&world());
+    assert!(matches!(
+        compression::deserialize_slice_compressed::<World>(&serialized),
+        Err(ReadError::NotCompressed)
+    ));
+}
+
+#[test]
+fn test_compressed_document_errors() {
+    let mut serialized = compression::to_vec_compressed(&world(), Codec::Deflate);
+    serialized[6] = 9;
+    assert!(matches!(
+        compression::deserialize_slice_compressed::<World>(&serialized),
+        Err(ReadError::UnknownCodec(9))
+    ));
+
+    serialized[6] = Codec::Deflate as u8;
+    serialized[10] = serialized[10].wrapping_sub(1);
+    assert!(matches!(
+        compression::deserialize_slice_compressed::<World>(&serialized),
+        Err(ReadError::DecompressionFailed)
+    ));
+
+    // A compressed length that is larger than the input fails without allocating it
+    serialized.truncate(15);
+    serialized[11..15].copy_from_slice(&u32::MAX.to_be_bytes());
+    assert!(matches!(
+        compression::deserialize_slice_compressed::<World>(&serialized),
+        Err(ReadError::EndOfInput)
+    ));
+}
+
+#[derive(Serializable, Debug, PartialEq, Default)]
+#[binver(domain = "corelib")]
+pub struct Core {
+    #[since(1.0.0)]
+    pub id: u32,
+    #[since(2.0.0)]
+    pub flags: u32,
+}
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct OuterCore {
+    #[since(0.0.1)]
+    #[binver(compress)]
+    pub items: Vec<Core>,
+}
+
+static CORE_DOMAINS: &[(&str, Version)] = &[("corelib", Version::new(2, 0, 0))];
+
+#[test]
+fn test_compressed_field_uses_domain_version() {
+    let outer = OuterCore {
+        items: vec![Core { id: 1, flags: 2 }, Core { id: 3, flags: 4 }],
+    };
+    let config = WriteConfig {
+        schema_version: Some(Version::new(0, 0, 1)),
+        domains: CORE_DOMAINS,
+        ..WriteConfig::default()
+    };
+    let serialized = binver::to_vec_with_config(&outer, config);
+    let deserialized: OuterCore = binver::deserialize_slice(&serialized).unwrap();
+    assert_eq!(deserialized, outer);
+}
+
+#[derive(Serializable, Debug, PartialEq, Default)]
+pub struct Inner {
+    #[since(0.0.1)]
+    pub a: u32,
+    #[binver(feature = "guilds")]
+    pub guild: u16,
+}
+
+#[derive(Serializable, Debug, PartialEq, Default)]
+pub enum InnerEvent {
+    #[default]
+    #[since(0.0.1)]
+    Join,
+    #[since(0.0.1)]
+    #[binver(feature = "guilds")]
+    JoinGuild,
+}
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct OuterFeatures {
+    #[since(0.0.1)]
+    #[binver(compress)]
+    pub inner: Vec<Inner>,
+    #[since(0.0.1)]
+    #[binver(compress)]
+    pub events: Vec<InnerEvent>,
+}
+
+#[test]
+fn test_compressed_field_uses_features() {
+    let outer = OuterFeatures {
+        inner: vec![Inner { a: 1, guild: 7 }],
+        events: vec![InnerEvent::Join, InnerEvent::JoinGuild],
+    };
+    let config = WriteConfig {
+        features: &["guilds"],
+        ..WriteConfig::default()
+    };
+    let serialized = binver::to_vec_with_config(&outer, config);
+    let deserialized: OuterFeatures = binver::deserialize_slice(&serialized).unwrap();
+    assert_eq!(deserialized, outer);
+}
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct OuterVersion {
+    #[since(0.0.1)]
+    #[binver(compress)]
+    pub versions: Vec<Version>,
+}
+
+#[test]
+fn test_compressed_field_uses_wire_revision() {
+    let outer = OuterVersion {
+        versions: vec![Version::new(1, 2, 3)],
+    };
+    let config = WriteConfig {
+        wire_revision: Some(1),
+        ..WriteConfig::default()
+    };
+    let serialized = binver::to_vec_with_config(&outer, config);
+    let deserialized: OuterVersion = binver::deserialize_slice(&serialized).unwrap();
+    assert_eq!(deserialized, outer);
+}
+
+#[test]
+fn test_compressed_document_with_config() {
+    let inner = Inner { a: 1, guild: 7 };
+    let config = WriteConfig {
+        schema_version: Some(Version::new(0, 0, 2)),
+        features: &["guilds"],
+        type_fingerprint: true,
+        ..WriteConfig::default()
+    };
+    let serialized =
+        compression::to_vec_compressed_with_config(&inner, Codec::Deflate, config).unwrap();
+    assert_eq!(
+        binver::sniff_with_magic(&serialized, &[]),
+        Some(Version::new(0, 0, 2))
+    );
+
+    let config = ReadConfig::default().with_type_fingerprint();
+    let deserialized: Inner =
+        compression::deserialize_slice_compressed_with_config(&serialized, config).unwrap();
+    assert_eq!(deserialized, inner);
+}
@@ -0,0 +1,142 @@
+#![cfg(feature = "std")]
+
+use binver::{
+    container::{ContainerReader, ContainerSlice, ContainerWriter},
+    ReadConfig, ReadError, Serializable, Version, WriteConfig,
+};
+use std::io::Cursor;
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct Asset<'a> {
+    #[since(0.0.1)]
+    pub name: &'a str,
+    #[since(0.0.1)]
+    pub data: &'a [u8],
+}
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct OwnedAsset {
+    #[since(0.0.1)]
+    pub name: String,
+    #[since(0.0.1)]
+    pub data: Vec<u8>,
+}
+
+fn bundle() -> Vec<u8> {
+    let mut writer = ContainerWriter::new(Vec::new()).unwrap();
+    writer
+        .insert(
+            "a",
+            &Asset {
+                name: "first",
+                data: &[1, 2, 3],
+            },
+        )
+        .unwrap();
+    writer
+        .insert(
+            "b",
+            &Asset {
+                name: "second",
+                data: &[4, 5],
+            },
+        )
+        .unwrap();
+    writer.finish().unwrap()
+}
+
+#[test]
+fn test_container_slice() {
+    let bundle = bundle();
+    assert_eq!(&bundle[..4], b"BVCT");
+    assert_eq!(&bundle[bundle.len() - 4..], b"BVCT");
+
+    let container = ContainerSlice::new(&bundle).unwrap();
+    let mut keys: Vec<_> = container.keys().collect();
+    keys.sort_unstable();
+    assert_eq!(keys, vec!["a", "b"]);
+
+    let b: Asset = container.get("b").unwrap().unwrap();
+    assert_eq!(
+        b,
+        Asset {
+            name: "second",
+            data: &[4, 5]
+        }
+    );
+    assert!(container.get::<Asset>("c").unwrap().is_none());
+}
+
+#[test]
+fn test_container_reader() {
+    let mut container = ContainerReader::new(Cursor::new(bundle())).unwrap();
+    let a: OwnedAsset = container.get("a").unwrap().unwrap();
+    assert_eq!(
+        a,
+        OwnedAsset {
+            name: String::from("first"),
+            data: vec![1, 2, 3]
+        }
+    );
+}
+
+#[test]
+fn test_container_invalid_magic() {
+    let mut bundle = bundle();
+    let len = bundle.len();
+    bundle[len - 1] = 0;
+    assert!(matches!(
+        ContainerSlice::new(&bundle),
+        Err(ReadError::InvalidMagic)
+    ));
+    assert!(matches!(
+        ContainerReader::new(Cursor::new(bundle)),
+        Err(ReadError::InvalidMagic)
+    ));
+}
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct Level {
+    #[since(0.0.1)]
+    pub id: u32,
+    #[since(0.0.2)]
+    pub name: String,
+}
+
+#[test]
+fn test_container_with_config() {
+    let write_config = WriteConfig {
+        schema_version: Some(Version::new(0, 0, 2)),
+        type_fingerprint: true,
+        ..WriteConfig::default()
+    };
+    let read_config = ReadConfig::default().with_type_fingerprint();
+    let level = Level {
+        id: 3,
+        name: String::from("caves"),
+    };
+    let mut writer = ContainerWriter::new(Vec::new()).unwrap();
+    writer
+        .insert_with_config("level", &level, write_config)
+        .unwrap();
+    let bundle = writer.finish().unwrap();
+
+    let container = ContainerSlice::new(&bundle).unwrap();
+    let entry = container.entry("level").unwrap().unwrap();
+    assert_eq!(
+        binver::sniff_with_magic(entry, &[]),
+        Some(Version::new(0, 0, 2))
+    );
+    let deserialized: Level = container
+        .get_with_config("level", read_config.clone())
+        .unwrap()
+        .unwrap();
+    assert_eq!(deserialized, level);
+
+    let mut reader = ContainerReader::new(Cursor::new(bundle)).unwrap();
+    let deserialized: Level = reader
+        .get_with_config("level", read_config)
+        .unwrap()
+        .unwrap();
+    assert_eq!(deserialized, level);
+}
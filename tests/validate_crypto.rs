@@ -0,0 +1,90 @@
+#![cfg(feature = "crypto")]
+
+use binver::{crypto, ReadConfig, ReadError, Serializable, Version, WriteConfig};
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct Credentials {
+    #[since(0.0.1)]
+    pub user: String,
+    #[since(0.0.1)]
+    pub token: String,
+}
+
+const KEY: [u8; crypto::KEY_SIZE] = [42; crypto::KEY_SIZE];
+
+fn credentials() -> Credentials {
+    Credentials {
+        user: String::from("Trangar"),
+        token: String::from("secret token"),
+    }
+}
+
+#[test]
+fn test_encrypted_roundtrip() {
+    let encrypted = crypto::to_vec_encrypted(&credentials(), &KEY);
+    assert_eq!(crypto::read_version(&encrypted).unwrap(), *binver::VERSION);
+    assert!(!encrypted.windows(12).any(|w| w == b"secret token"));
+
+    let decrypted: Credentials = crypto::deserialize_slice_encrypted(&encrypted, &KEY).unwrap();
+    assert_eq!(decrypted, credentials());
+
+    // Every document gets a new nonce
+    assert_ne!(encrypted, crypto::to_vec_encrypted(&credentials(), &KEY));
+}
+
+#[test]
+fn test_encrypted_tampering() {
+    let encrypted = crypto::to_vec_encrypted(&credentials(), &KEY);
+
+    for index in [1, 10, encrypted.len() - 1] {
+        let mut tampered = encrypted.clone();
+        tampered[index] ^= 1;
+        assert!(matches!(
+            crypto::deserialize_slice_encrypted::<Credentials>(&tampered, &KEY),
+            Err(ReadError::DecryptionFailed)
+        ));
+    }
+
+    let wrong_key = [0u8; crypto::KEY_SIZE];
+    assert!(matches!(
+        crypto::deserialize_slice_encrypted::<Credentials>(&encrypted, &wrong_key),
+        Err(ReadError::DecryptionFailed)
+    ));
+}
+
+#[test]
+fn test_encrypted_with_config() {
+    let config = WriteConfig {
+        schema_version: Some(Version::new(0, 0, 2)),
+        ..WriteConfig::default()
+    };
+    let encrypted = crypto::to_vec_encrypted_with_config(&credentials(), &KEY, config).unwrap();
+    assert_eq!(
+        crypto::read_version(&encrypted).unwrap(),
+        Version::new(0, 0, 2)
+    );
+    let decrypted: Credentials = crypto::deserialize_slice_encrypted(&encrypted, &KEY).unwrap();
+    assert_eq!(decrypted, credentials());
+}
+
+#[test]
+fn test_encrypted_with_magic() {
+    let config = WriteConfig {
+        magic: Some(b"CRED"),
+        schema_version: Some(Version::new(0, 0, 2)),
+        ..WriteConfig::default()
+    };
+    let encrypted = crypto::to_vec_encrypted_with_config(&credentials(), &KEY, config).unwrap();
+    let read_options = ReadConfig::default().with_magic(b"CRED");
+    assert_eq!(
+        crypto::read_version_with_config(&encrypted, read_options.clone()).unwrap(),
+        Version::new(0, 0, 2)
+    );
+    assert!(matches!(
+        crypto::read_version_with_config(&encrypted, ReadConfig::default().with_magic(b"SAVE")),
+        Err(ReadError::InvalidMagic)
+    ));
+    let decrypted: Credentials =
+        crypto::deserialize_slice_encrypted_with_config(&encrypted, &KEY, read_options).unwrap();
+    assert_eq!(decrypted, credentials());
+}
@@ -0,0 +1,176 @@
+use binver::{ReadError, Serializable, Version, WriteConfig, WriteError};
+
+/// A type of a shared library, which is versioned independently of the application
+#[derive(Serializable, Debug, PartialEq, Default)]
+#[binver(domain = "corelib")]
+pub struct Color {
+    #[since(1.0.0)]
+    pub rgb: u32,
+    #[since(1.1.0)]
+    pub alpha: u8,
+}
+
+#[derive(Serializable, Debug, PartialEq, Default)]
+#[binver(domain = "corelib")]
+pub enum Shape {
+    #[default]
+    #[since(1.0.0)]
+    Point,
+    #[since(1.1.0)]
+    Circle(#[since(1.1.0)] u16),
+}
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct Scene {
+    #[since(0.0.1)]
+    pub id: u32,
+    #[since(0.0.1)]
+    pub color: Color,
+    #[since(0.0.1)]
+    pub shape: Shape,
+}
+
+static DOMAINS: &[(&str, Version)] = &[("corelib", Version::new(1, 1, 0))];
+
+#[test]
+fn test_domain_roundtrip() {
+    let scene = Scene {
+        id: 1,
+        color: Color {
+            rgb: 0xFF0000,
+            alpha: 128,
+        },
+        shape: Shape::Circle(5),
+    };
+    let config = WriteConfig {
+        schema_version: Some(Version::new(0, 0, 2)),
+        domains: DOMAINS,
+        ..WriteConfig::default()
+    };
+    let mut slice = [0u8; 64];
+    let length = binver::write_to_slice_with_config(&mut slice, &scene, config).unwrap();
+    assert_eq!(
+        &slice[..17],
+        &[
+            0x83, 0, 0, 2, 0, 0, // revision with the domain flag, version 0.0.2
+            1, 7, b'c', b'o', b'r', b'e', b'l', b'i', b'b', 1, 1, // corelib 1.1.0
+        ]
+    );
+    assert_eq!(
+        binver::sniff_with_magic(&slice, &[]),
+        Some(Version::new(0, 0, 2))
+    );
+
+    let deserialized: Scene = binver::deserialize_slice(&slice[..length]).unwrap();
+    assert_eq!(deserialized, scene);
+}
+
+#[test]
+fn test_older_domain_version() {
+    let bytes = [
+        0x83, 0, 0, 2, 0, 0, // revision with the domain flag, version 0.0.2
+        1, 7, b'c', b'o', b'r', b'e', b'l', b'i', b'b', 1, 0, 0, 0, 0, // corelib 1.0.0
+        0, 0, 0, 1, // id
+        0, 0xFF, 0, 0, // color, without alpha
+        0, 0, // Shape::Point
+    ];
+    let deserialized: Scene = binver::deserialize_slice(&bytes).unwrap();
+    assert_eq!(
+        deserialized,
+        Scene {
+            id: 1,
+            color: Color {
+                rgb: 0xFF0000,
+                alpha: 0
+            },
+            shape: Shape::Point,
+        }
+    );
+
+    // `Shape::Circle` did not exist in corelib 1.0.0
+    let mut bytes = bytes;
+    bytes[bytes.len() - 1] = 1;
+    assert!(matches!(
+        binver::deserialize_slice::<Scene>(&bytes),
+        Err(ReadError::UnknownVariant(1))
+    ));
+}
+
+#[test]
+fn test_missing_domain_uses_schema_version() {
+    let config = WriteConfig {
+        schema_version: Some(Version::new(1, 1, 0)),
+        ..WriteConfig::default()
+    };
+    let color = Color {
+        rgb: 0x00FF00,
+        alpha: 255,
+    };
+    let mut slice = [0u8; 64];
+    let length = binver::write_to_slice_with_config(&mut slice, &color, config).unwrap();
+    let deserialized: Color = binver::deserialize_slice(&slice[..length]).unwrap();
+    assert_eq!(deserialized, color);
+}
+
+#[test]
+fn test_runtime_domains_and_features() {
+    // Domains and features can come from data that is only known at runtime, e.g. a `lazy_static` version
+    let corelib_version = Version::parse("1.0.0").unwrap();
+    let domains = [("corelib", corelib_version)];
+    let features = [String::from("guilds")];
+    let features = [features[0].as_str()];
+    let config = WriteConfig {
+        schema_version: Some(Version::new(0, 0, 2)),
+        domains: &domains,
+        features: &features,
+        ..WriteConfig::default()
+    };
+    let color = Color {
+        rgb: 0x0000FF,
+        alpha: 64,
+    };
+    let mut slice = [0u8; 64];
+    let length = binver::write_to_slice_with_config(&mut slice, &color, config).unwrap();
+    let deserialized: Color = binver::deserialize_slice(&slice[..length]).unwrap();
+    // `alpha` was added in corelib 1.1.0, so it is not read
+    assert_eq!(
+        deserialized,
+        Color {
+            rgb: 0x0000FF,
+            alpha: 0,
+        }
+    );
+}
+
+#[test]
+fn test_domains_need_revision_2() {
+    let config = WriteConfig {
+        domains: DOMAINS,
+        wire_revision: Some(1),
+        ..WriteConfig::default()
+    };
+    let mut slice = [0u8; 64];
+    assert!(matches!(
+        binver::write_to_slice_with_config(&mut slice, &Color::default(), config),
+        Err(WriteError::UnsupportedWireRevision(1))
+    ));
+}
+
+#[test]
+fn test_huge_domain_length() {
+    // The length of the first domain name is larger than the data
+    let mut data = vec![0x83, 0, 0, 0, 0, 0, 1];
+    data.extend_from_slice(&[0xFF; 8]);
+    data.push(0x01);
+    assert!(matches!(
+        binver::deserialize_slice::<u8>(&data),
+        Err(ReadError::EndOfInput)
+    ));
+
+    // The same for the name of a feature
+    data[0] = 0x43;
+    assert!(matches!(
+        binver::deserialize_slice::<u8>(&data),
+        Err(ReadError::EndOfInput)
+    ));
+}
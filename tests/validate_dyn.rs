@@ -0,0 +1,71 @@
+use binver::{ReadError, ReadResult, Reader, Serializable, Version, WriteResult, Writer};
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct Test<'a> {
+    #[since(0.0.1)]
+    pub id: u32,
+    #[since(0.0.2)]
+    pub name: &'a str,
+}
+
+struct BufferWriter(Vec<u8>);
+
+impl Writer for BufferWriter {
+    fn write(&mut self, bytes: &[u8]) -> WriteResult {
+        self.0.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+struct BufferReader<'a> {
+    slice: &'a [u8],
+}
+
+impl<'a> Reader<'a> for BufferReader<'a> {
+    fn version(&self) -> Version {
+        Version::new(0, 0, 2)
+    }
+
+    fn read(&mut self, bytes: &mut [u8]) -> ReadResult {
+        let slice = self.read_slice(bytes.len())?;
+        bytes.copy_from_slice(slice);
+        Ok(())
+    }
+
+    fn read_slice(&mut self, len: usize) -> ReadResult<&'a [u8]> {
+        if self.slice.len() < len {
+            return Err(ReadError::EndOfInput);
+        }
+        let (slice, rest) = self.slice.split_at(len);
+        self.slice = rest;
+        Ok(slice)
+    }
+}
+
+#[test]
+fn test_trait_objects() {
+    let test = Test { id: 5, name: "dyn" };
+
+    let mut writer = BufferWriter(Vec::new());
+    let dyn_writer: &mut dyn Writer = &mut writer;
+    test.serialize(dyn_writer).unwrap();
+    assert_eq!(writer.0, [0, 0, 0, 5, 0, 0, 0, 3, b'd', b'y', b'n']);
+
+    let mut reader = BufferReader { slice: &writer.0 };
+    let dyn_reader: &mut dyn Reader = &mut reader;
+    let deserialized = Test::deserialize(dyn_reader).unwrap();
+    assert_eq!(deserialized, test);
+}
+
+#[test]
+fn test_generic() {
+    let test = Test { id: 5, name: "dyn" };
+
+    let mut writer = BufferWriter(Vec::new());
+    test.serialize(&mut writer).unwrap();
+
+    let mut reader = BufferReader { slice: &writer.0 };
+    let deserialized = Test::deserialize(&mut reader).unwrap();
+    assert_eq!(deserialized, test);
+    assert!(reader.slice.is_empty());
+}
@@ -0,0 +1,141 @@
+use binver::{Serializable, Version, WriteConfig, WriteError};
+
+#[derive(Serializable, Debug, PartialEq, Default)]
+pub struct Player {
+    #[since(0.0.1)]
+    pub id: u32,
+    // Backported to the LTS line, which has a lower version than 0.2.0
+    #[binver(feature = "guilds")]
+    pub guild: u16,
+    #[since(0.2.0)]
+    #[binver(feature = "pets")]
+    pub pet: u8,
+}
+
+#[derive(Serializable, Debug, PartialEq)]
+pub enum Event {
+    #[since(0.0.1)]
+    Join(#[since(0.0.1)] u32),
+    #[since(0.0.1)]
+    #[binver(feature = "guilds")]
+    JoinGuild(#[since(0.0.1)] u16),
+    // Only gated by a feature, added at the bottom after variants with a version
+    #[binver(feature = "pets")]
+    Adopt(#[since(0.0.1)] u8),
+    #[since(0.0.2)]
+    Leave,
+}
+
+static FEATURES: &[&str] = &["guilds"];
+
+#[test]
+fn test_feature_enabled() {
+    let player = Player {
+        id: 1,
+        guild: 2,
+        pet: 0,
+    };
+    let config = WriteConfig {
+        schema_version: Some(Version::new(0, 1, 0)),
+        features: FEATURES,
+        ..WriteConfig::default()
+    };
+    let mut slice = [0u8; 64];
+    let length = binver::write_to_slice_with_config(&mut slice, &player, config).unwrap();
+    assert_eq!(
+        &slice[..length],
+        &[
+            0x43, 0, 1, 0, 0, 0, // revision with the feature flag, version 0.1.0
+            1, 6, b'g', b'u', b'i', b'l', b'd', b's', // features
+            0, 0, 0, 1, // id
+            0, 2, // guild
+        ]
+    );
+    let deserialized: Player = binver::deserialize_slice(&slice[..length]).unwrap();
+    assert_eq!(deserialized, player);
+}
+
+#[test]
+fn test_feature_disabled() {
+    let player = Player {
+        id: 1,
+        guild: 2,
+        pet: 3,
+    };
+    let config = WriteConfig {
+        schema_version: Some(Version::new(0, 2, 0)),
+        ..WriteConfig::default()
+    };
+    let mut slice = [0u8; 64];
+    let length = binver::write_to_slice_with_config(&mut slice, &player, config).unwrap();
+    // Neither `guild` nor `pet` are written
+    assert_eq!(&slice[6..length], &[0, 0, 0, 1]);
+    let deserialized: Player = binver::deserialize_slice(&slice[..length]).unwrap();
+    assert_eq!(
+        deserialized,
+        Player {
+            id: 1,
+            guild: 0,
+            pet: 0
+        }
+    );
+}
+
+#[test]
+fn test_feature_variant() {
+    let config = WriteConfig {
+        features: FEATURES,
+        ..WriteConfig::default()
+    };
+    let mut slice = [0u8; 64];
+    let length =
+        binver::write_to_slice_with_config(&mut slice, &Event::JoinGuild(5), config).unwrap();
+    let deserialized: Event = binver::deserialize_slice(&slice[..length]).unwrap();
+    assert_eq!(deserialized, Event::JoinGuild(5));
+
+    assert!(matches!(
+        binver::write_to_slice(&mut slice, &Event::JoinGuild(5)),
+        Err(WriteError::FeatureDisabled("guilds"))
+    ));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_feature_variant_in_vec() {
+    assert!(matches!(
+        binver::try_to_vec(&Event::JoinGuild(5)),
+        Err(WriteError::FeatureDisabled("guilds"))
+    ));
+    #[cfg(feature = "checksum")]
+    assert!(matches!(
+        binver::to_vec_checked(&Event::JoinGuild(5)),
+        Err(WriteError::FeatureDisabled("guilds"))
+    ));
+
+    let mut container = binver::container::ContainerWriter::new(Vec::new()).unwrap();
+    assert!(matches!(
+        container.insert("event", &Event::JoinGuild(5)),
+        Err(WriteError::FeatureDisabled("guilds"))
+    ));
+
+    let config = WriteConfig {
+        features: FEATURES,
+        ..WriteConfig::default()
+    };
+    let serialized = binver::try_to_vec_with_config(&Event::JoinGuild(5), config).unwrap();
+    let deserialized: Event = binver::deserialize_slice(&serialized).unwrap();
+    assert_eq!(deserialized, Event::JoinGuild(5));
+}
+
+#[test]
+fn test_feature_only_variant() {
+    let config = WriteConfig {
+        schema_version: Some(Version::new(0, 0, 1)),
+        features: &["pets"],
+        ..WriteConfig::default()
+    };
+    let mut slice = [0u8; 64];
+    let length = binver::write_to_slice_with_config(&mut slice, &Event::Adopt(3), config).unwrap();
+    let deserialized: Event = binver::deserialize_slice(&slice[..length]).unwrap();
+    assert_eq!(deserialized, Event::Adopt(3));
+}
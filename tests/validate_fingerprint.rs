@@ -0,0 +1,188 @@
+use binver::{ReadConfig, ReadError, ReadOptions, Serializable, WriteConfig};
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct Player {
+    #[since(0.0.1)]
+    pub id: u32,
+}
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct Inventory {
+    #[since(0.0.1)]
+    pub id: u32,
+}
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct PlayerV2 {
+    #[since(0.0.1)]
+    pub id: u32,
+    #[since(0.0.2)]
+    pub level: u32,
+}
+
+#[derive(Serializable, Debug, PartialEq)]
+pub enum Item<'a> {
+    #[since(0.0.1)]
+    Sword,
+    #[since(0.0.1)]
+    Named(#[since(0.0.1)] &'a str),
+}
+
+mod aliased {
+    use binver::Serializable;
+
+    pub type Id = u32;
+
+    #[derive(Serializable)]
+    pub struct Player {
+        #[since(0.0.1)]
+        pub id: Id,
+    }
+}
+
+mod nested_v1 {
+    use binver::Serializable;
+
+    #[derive(Serializable, Default)]
+    pub struct Stats {
+        #[since(0.0.1)]
+        pub health: u32,
+    }
+
+    #[derive(Serializable)]
+    pub struct Player {
+        #[since(0.0.1)]
+        pub stats: Stats,
+    }
+}
+
+mod nested_v2 {
+    use binver::Serializable;
+
+    #[derive(Serializable, Default)]
+    pub struct Stats {
+        #[since(0.0.1)]
+        pub health: u64,
+    }
+
+    #[derive(Serializable)]
+    pub struct Player {
+        #[since(0.0.1)]
+        pub stats: Stats,
+    }
+}
+
+fn write_config() -> WriteConfig<'static> {
+    WriteConfig {
+        type_fingerprint: true,
+        ..WriteConfig::default()
+    }
+}
+
+fn read_config() -> ReadOptions {
+    ReadConfig::default().with_type_fingerprint()
+}
+
+#[test]
+fn test_fingerprint_uses_field_types() {
+    // The fingerprint depends on the type itself, not on how it is spelled
+    assert_eq!(aliased::Player::FINGERPRINT, Player::FINGERPRINT);
+    // Changing a nested type changes the fingerprint of every type that contains it
+    assert_ne!(
+        nested_v1::Player::FINGERPRINT,
+        nested_v2::Player::FINGERPRINT
+    );
+}
+
+#[test]
+fn test_fingerprint_is_stable() {
+    // These values are part of the wire format and must never change
+    assert_eq!(Player::FINGERPRINT, 0xa161_c3bb_1333_a423);
+    assert_eq!(<Item as Serializable>::FINGERPRINT, 0x3bcd_a72e_6911_8dd3);
+    assert_ne!(Player::FINGERPRINT, Inventory::FINGERPRINT);
+    assert_ne!(Player::FINGERPRINT, PlayerV2::FINGERPRINT);
+    assert_ne!(u8::FINGERPRINT, u16::FINGERPRINT);
+}
+
+#[test]
+fn test_fingerprint_roundtrip() {
+    let mut slice = [0u8; 64];
+    let length =
+        binver::write_to_slice_with_config(&mut slice, &Player { id: 5 }, write_config()).unwrap();
+    assert_eq!(length, 6 + 8 + 4);
+    assert_eq!(&slice[6..14], &Player::FINGERPRINT.to_be_bytes());
+
+    let player: Player =
+        binver::deserialize_slice_with_config(&slice[..length], read_config()).unwrap();
+    assert_eq!(player, Player { id: 5 });
+}
+
+#[test]
+fn test_fingerprint_mismatch() {
+    let mut slice = [0u8; 64];
+    let length =
+        binver::write_to_slice_with_config(&mut slice, &Player { id: 5 }, write_config()).unwrap();
+
+    // Without the fingerprint, this would succeed
+    match binver::deserialize_slice_with_config::<Inventory>(&slice[..length], read_config()) {
+        Err(ReadError::TypeMismatch { expected, found }) => {
+            assert_eq!(expected, Inventory::FINGERPRINT);
+            assert_eq!(found, Player::FINGERPRINT);
+        }
+        x => panic!("Expected a type mismatch, got {:?}", x),
+    }
+}
+
+/// Types that contain themselves through a `Vec`, which needs `std`
+#[cfg(feature = "std")]
+mod recursive {
+    use super::{read_config, write_config};
+    use binver::Serializable;
+
+    #[derive(Serializable, Debug, PartialEq)]
+    pub struct Node {
+        #[since(0.0.1)]
+        pub value: u32,
+        #[since(0.0.1)]
+        pub children: Vec<Node>,
+    }
+
+    #[derive(Serializable, Debug, PartialEq, Default)]
+    pub struct Folder {
+        #[since(0.0.1)]
+        pub files: Vec<File>,
+    }
+
+    #[derive(Serializable, Debug, PartialEq, Default)]
+    pub struct File {
+        #[since(0.0.1)]
+        pub folder: Option<Folder>,
+    }
+
+    #[test]
+    fn test_recursive_types() {
+        let node = Node {
+            value: 1,
+            children: vec![Node {
+                value: 2,
+                children: Vec::new(),
+            }],
+        };
+        let serialized = binver::to_vec_with_config(&node, write_config());
+        let deserialized: Node =
+            binver::deserialize_slice_with_config(&serialized, read_config()).unwrap();
+        assert_eq!(deserialized, node);
+
+        // Types that contain each other
+        let folder = Folder {
+            files: vec![File {
+                folder: Some(Folder::default()),
+            }],
+        };
+        let serialized = binver::to_vec_with_config(&folder, write_config());
+        let deserialized: Folder =
+            binver::deserialize_slice_with_config(&serialized, read_config()).unwrap();
+        assert_eq!(deserialized, folder);
+        assert_ne!(Folder::FINGERPRINT, File::FINGERPRINT);
+    }
+}
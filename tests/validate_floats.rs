@@ -0,0 +1,96 @@
+use binver::{ByteOrder, IntegerEncoding, Serializable, WriteConfig};
+
+#[derive(Serializable, Debug, PartialEq, Default)]
+pub struct Body {
+    #[since(0.0.1)]
+    pub x: f32,
+    #[since(0.0.1)]
+    pub velocity: f64,
+    #[since(0.0.2)]
+    pub health: f32,
+}
+
+const BODY: Body = Body {
+    x: 1.5,
+    velocity: -2.25,
+    health: 100.0,
+};
+
+#[test]
+fn test_floats() {
+    let mut slice = [0u8; 64];
+    let length = binver::write_to_slice(&mut slice, &BODY).unwrap();
+    assert_eq!(&slice[6..10], &1.5f32.to_bits().to_be_bytes());
+    assert_eq!(&slice[10..18], &(-2.25f64).to_bits().to_be_bytes());
+    assert_eq!(length, 6 + 4 + 8 + 4);
+    let deserialized: Body = binver::deserialize_slice(&slice[..length]).unwrap();
+    assert_eq!(deserialized, BODY);
+    assert_eq!(Body::MAX_SERIALIZED_SIZE, Some(16));
+}
+
+#[test]
+fn test_special_values() {
+    for value in [
+        0.0f64,
+        -0.0,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::MIN_POSITIVE,
+        f64::MAX,
+        f64::EPSILON / 2.0,
+    ] {
+        let mut slice = [0u8; 16];
+        let length = binver::write_to_slice(&mut slice, &value).unwrap();
+        let deserialized: f64 = binver::deserialize_slice(&slice[..length]).unwrap();
+        assert_eq!(deserialized.to_bits(), value.to_bits());
+    }
+}
+
+#[test]
+fn test_little_endian_and_varint() {
+    // Floats always have their full width, and follow the byte order of the document
+    let config = WriteConfig {
+        byte_order: ByteOrder::LittleEndian,
+        integer_encoding: IntegerEncoding::Varint,
+        ..WriteConfig::default()
+    };
+    let mut slice = [0u8; 64];
+    let length = binver::write_to_slice_with_config(&mut slice, &BODY, config).unwrap();
+    assert_eq!(&slice[6..10], &1.5f32.to_bits().to_le_bytes());
+    assert_eq!(length, 6 + 4 + 8 + 4);
+    let deserialized: Body = binver::deserialize_slice(&slice[..length]).unwrap();
+    assert_eq!(deserialized, BODY);
+}
+
+#[test]
+fn test_nan_payload_is_kept() {
+    let nan = f32::from_bits(0xFFC0_0001);
+    let mut slice = [0u8; 16];
+    let length = binver::write_to_slice(&mut slice, &nan).unwrap();
+    let deserialized: f32 = binver::deserialize_slice(&slice[..length]).unwrap();
+    assert_eq!(deserialized.to_bits(), 0xFFC0_0001);
+}
+
+#[test]
+fn test_canonical_nan() {
+    let config = WriteConfig {
+        canonical_nan: true,
+        ..WriteConfig::default()
+    };
+    let mut first = [0u8; 16];
+    let mut second = [0u8; 16];
+    let length = binver::write_to_slice_with_config(
+        &mut first,
+        &f32::from_bits(0xFFC0_0001),
+        config.clone(),
+    )
+    .unwrap();
+    binver::write_to_slice_with_config(&mut second, &-f32::NAN, config.clone()).unwrap();
+    assert_eq!(first, second);
+    assert_eq!(&first[6..length], &[0x7F, 0xC0, 0, 0]);
+
+    let length = binver::write_to_slice_with_config(&mut first, &f64::NAN, config).unwrap();
+    let deserialized: f64 = binver::deserialize_slice(&first[..length]).unwrap();
+    assert!(deserialized.is_nan());
+    assert_eq!(deserialized.to_bits(), 0x7FF8_0000_0000_0000);
+}
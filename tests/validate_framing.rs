@@ -0,0 +1,140 @@
+#![cfg(feature = "std")]
+
+use binver::{framing::FrameCodec, ReadError, Serializable, Version, WriteConfig, WriteError};
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct Message {
+    #[since(0.0.1)]
+    pub id: u32,
+    #[since(0.0.1)]
+    pub body: String,
+}
+
+fn message(id: u32) -> Message {
+    Message {
+        id,
+        body: format!("message {}", id),
+    }
+}
+
+#[test]
+fn test_frame_partial_buffer() {
+    let codec = FrameCodec::new();
+    let mut encoded = Vec::new();
+    codec.encode(&message(1), &mut encoded).unwrap();
+    codec.encode(&message(2), &mut encoded).unwrap();
+
+    let mut buffer = Vec::new();
+    let mut received = Vec::new();
+    for byte in encoded {
+        buffer.push(byte);
+        while let Some(message) = codec.decode(&mut buffer).unwrap() {
+            received.push(message);
+        }
+    }
+    assert!(buffer.is_empty());
+    assert_eq!(received, vec![message(1), message(2)]);
+}
+
+#[test]
+fn test_frame_without_version_header() {
+    let mut codec = FrameCodec::new();
+    codec.version_header = false;
+
+    let mut buffer = Vec::new();
+    codec.encode(&message(3), &mut buffer).unwrap();
+    assert_eq!(&buffer[..4], &(4u32 + 4 + 9).to_be_bytes());
+    assert_eq!(&buffer[4..8], &3u32.to_be_bytes());
+
+    assert_eq!(codec.decode(&mut buffer).unwrap(), Some(message(3)));
+}
+
+#[test]
+fn test_frame_io_loop() {
+    let codec = FrameCodec::new();
+    let mut stream = Vec::new();
+    for id in 0..3 {
+        codec.write_to(&mut stream, &message(id)).unwrap();
+    }
+
+    let mut reader = &stream[..];
+    let mut received = Vec::new();
+    while let Some(message) = codec.read_from(&mut reader).unwrap() {
+        received.push(message);
+    }
+    assert_eq!(received, vec![message(0), message(1), message(2)]);
+
+    let mut truncated = &stream[..stream.len() - 1];
+    codec.read_from(&mut truncated).unwrap();
+    codec.read_from(&mut truncated).unwrap();
+    assert!(matches!(
+        codec.read_from(&mut truncated),
+        Err(ReadError::EndOfInput)
+    ));
+}
+
+#[test]
+fn test_frame_max_size() {
+    let mut codec = FrameCodec::new();
+    codec.max_frame_size = 16;
+
+    let mut buffer = Vec::new();
+    assert!(matches!(
+        codec.encode(&message(100), &mut buffer),
+        Err(WriteError::FrameTooLarge(25))
+    ));
+    assert!(buffer.is_empty());
+
+    buffer.extend_from_slice(&1000u32.to_be_bytes());
+    assert!(matches!(
+        codec.decode(&mut buffer),
+        Err(ReadError::FrameTooLarge(1000))
+    ));
+}
+
+#[cfg(feature = "tokio-codec")]
+#[test]
+fn test_frame_tokio_codec() {
+    use bytes::BytesMut;
+    use tokio_util::codec::{Decoder, Encoder};
+
+    let mut codec = FrameCodec::new();
+    let mut buffer = BytesMut::new();
+    Encoder::encode(&mut codec, message(1), &mut buffer).unwrap();
+
+    let mut partial = buffer.split_to(10);
+    assert_eq!(Decoder::decode(&mut codec, &mut partial).unwrap(), None);
+    assert_eq!(partial.len(), 10);
+
+    partial.unsplit(buffer);
+    assert_eq!(
+        Decoder::decode(&mut codec, &mut partial).unwrap(),
+        Some(message(1))
+    );
+    assert!(partial.is_empty());
+}
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct Update {
+    #[since(0.0.1)]
+    pub id: u32,
+    #[since(0.0.2)]
+    pub delta: i16,
+}
+
+#[test]
+fn test_frame_write_config() {
+    let mut codec = FrameCodec::new();
+    codec.write_config = WriteConfig {
+        schema_version: Some(Version::new(0, 0, 2)),
+        ..WriteConfig::default()
+    };
+    let update = Update { id: 1, delta: -4 };
+    let mut buffer = Vec::new();
+    codec.encode(&update, &mut buffer).unwrap();
+    assert_eq!(
+        binver::sniff_with_magic(&buffer[4..], &[]),
+        Some(Version::new(0, 0, 2))
+    );
+    assert_eq!(codec.decode(&mut buffer).unwrap(), Some(update));
+}
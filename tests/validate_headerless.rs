@@ -0,0 +1,55 @@
+use binver::{ReadConfig, ReadError, Serializable, Version};
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct Row {
+    #[since(0.0.1)]
+    pub id: u32,
+    #[since(0.0.2)]
+    pub score: u16,
+}
+
+#[test]
+fn test_write_without_header() {
+    let mut slice = [0u8; 64];
+    let length =
+        binver::write_to_slice_without_header(&mut slice, &Row { id: 1, score: 2 }).unwrap();
+    assert_eq!(&slice[..length], &[0, 0, 0, 1, 0, 2]);
+
+    let deserialized: Row =
+        binver::deserialize_slice_with_version(&slice[..length], Version::new(0, 0, 2)).unwrap();
+    assert_eq!(deserialized, Row { id: 1, score: 2 });
+}
+
+#[test]
+fn test_read_with_older_version() {
+    // Written by a version that did not have `score` yet
+    let bytes = [0, 0, 0, 1];
+    let deserialized: Row =
+        binver::deserialize_slice_with_version(&bytes, Version::new(0, 0, 1)).unwrap();
+    assert_eq!(deserialized, Row { id: 1, score: 0 });
+
+    let config = ReadConfig {
+        error_on_trailing_bytes: true,
+    };
+    assert!(matches!(
+        binver::deserialize_slice_with_version_and_config::<Row>(
+            &[0, 0, 0, 1, 0, 2],
+            Version::new(0, 0, 1),
+            config
+        ),
+        Err(ReadError::TrailingBytes(2))
+    ));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_to_vec_without_header() {
+    let row = Row { id: 3, score: 4 };
+    let bytes = binver::to_vec_without_header(&row).unwrap();
+    assert_eq!(bytes.len(), 6);
+    assert_eq!(&binver::to_vec(&row)[6..], &bytes[..]);
+
+    let deserialized: Row =
+        binver::deserialize_slice_with_version(&bytes, binver::VERSION.clone()).unwrap();
+    assert_eq!(deserialized, row);
+}
@@ -0,0 +1,67 @@
+use binver::{ReadConfig, ReadError, Serializable, WriteConfig};
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct Test {
+    #[since(0.0.1)]
+    pub id: u32,
+}
+
+#[test]
+fn test_magic_roundtrip() {
+    let write_config = WriteConfig {
+        magic: Some(b"SAVE"),
+        ..WriteConfig::default()
+    };
+    let mut slice = [0u8; 64];
+    let length =
+        binver::write_to_slice_with_config(&mut slice, &Test { id: 5 }, write_config).unwrap();
+    assert_eq!(length, 4 + 6 + 4);
+    assert_eq!(&slice[..4], b"SAVE");
+
+    let read_config = ReadConfig::default().with_magic(b"SAVE");
+    let deserialized: Test =
+        binver::deserialize_slice_with_config(&slice[..length], read_config).unwrap();
+    assert_eq!(deserialized, Test { id: 5 });
+
+    let read_config = ReadConfig::default().with_magic(b"LOAD");
+    assert!(matches!(
+        binver::deserialize_slice_with_config::<Test>(&slice[..length], read_config),
+        Err(ReadError::InvalidMagic)
+    ));
+}
+
+#[test]
+fn test_sniff() {
+    let write_config = WriteConfig {
+        magic: Some(binver::MAGIC),
+        ..WriteConfig::default()
+    };
+    let mut slice = [0u8; 64];
+    let length =
+        binver::write_to_slice_with_config(&mut slice, &Test { id: 5 }, write_config).unwrap();
+    assert_eq!(
+        binver::sniff(&slice[..length]),
+        Some(binver::VERSION.clone())
+    );
+    assert_eq!(binver::sniff(&slice[..8]), None);
+    assert_eq!(binver::sniff(b"random data"), None);
+
+    let length = binver::write_to_slice(&mut slice, &Test { id: 5 }).unwrap();
+    assert_eq!(binver::sniff(&slice[..length]), None);
+    assert_eq!(
+        binver::sniff_with_magic(&slice[..length], &[]),
+        Some(binver::VERSION.clone())
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_std_magic() {
+    let write_config = WriteConfig {
+        magic: Some(binver::MAGIC),
+        ..WriteConfig::default()
+    };
+    let serialized = binver::to_vec_with_config(&Test { id: 5 }, write_config);
+    assert!(serialized.starts_with(b"BINVER"));
+    assert_eq!(binver::sniff(&serialized), Some(binver::VERSION.clone()));
+}
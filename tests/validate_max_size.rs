@@ -0,0 +1,105 @@
+use binver::{Serializable, Version};
+
+#[derive(Serializable, Debug, PartialEq, Default)]
+pub struct Telemetry {
+    #[since(0.0.1)]
+    pub temperature: i16,
+    #[since(0.0.1)]
+    pub uptime: u32,
+    #[since(0.0.2)]
+    pub online: bool,
+}
+
+#[derive(Serializable, Debug, PartialEq, Default)]
+pub enum Event {
+    #[default]
+    #[since(0.0.1)]
+    Boot,
+    #[since(0.0.1)]
+    Reading {
+        #[since(0.0.1)]
+        sensor: u8,
+        #[since(0.0.1)]
+        value: u64,
+    },
+    #[since(0.0.2)]
+    Error(#[since(0.0.2)] u16),
+}
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct Message {
+    #[since(0.0.1)]
+    pub telemetry: Telemetry,
+    #[since(0.0.1)]
+    pub event: Event,
+}
+
+#[derive(Serializable)]
+pub struct Named<'a> {
+    #[since(0.0.1)]
+    pub id: u32,
+    #[since(0.0.1)]
+    pub name: &'a str,
+}
+
+#[test]
+fn test_max_serialized_size() {
+    assert_eq!(u8::MAX_SERIALIZED_SIZE, Some(1));
+    assert_eq!(bool::MAX_SERIALIZED_SIZE, Some(1));
+    assert_eq!(u16::MAX_SERIALIZED_SIZE, Some(2));
+    assert_eq!(u64::MAX_SERIALIZED_SIZE, Some(8));
+    assert_eq!(Telemetry::MAX_SERIALIZED_SIZE, Some(2 + 4 + 1));
+    // The variant index, and the largest variant
+    assert_eq!(Event::MAX_SERIALIZED_SIZE, Some(2 + 1 + 8));
+    assert_eq!(Message::MAX_SERIALIZED_SIZE, Some(7 + 11));
+}
+
+#[test]
+fn test_unbounded_size() {
+    assert_eq!(<&str>::MAX_SERIALIZED_SIZE, None);
+    assert_eq!(Named::MAX_SERIALIZED_SIZE, None);
+}
+
+#[test]
+fn test_max_size_is_an_upper_bound() {
+    for event in [
+        Event::Boot,
+        Event::Reading {
+            sensor: 3,
+            value: u64::MAX,
+        },
+        Event::Error(404),
+    ] {
+        let size = binver::serialized_size_without_header(&event).unwrap();
+        assert!(size <= Event::MAX_SERIALIZED_SIZE.unwrap());
+    }
+}
+
+#[test]
+fn test_encode_to_array() {
+    let message = Message {
+        telemetry: Telemetry {
+            temperature: -40,
+            uptime: 86_400,
+            online: true,
+        },
+        event: Event::Error(7),
+    };
+    let bytes: [u8; 18] = binver::encode_to_array(&message).unwrap();
+    assert_eq!(
+        &bytes[..11],
+        &[
+            0xFF, 0xD8, // temperature
+            0, 1, 0x51, 0x80, // uptime
+            1,    // online
+            0, 2, // variant
+            0, 7, // error
+        ]
+    );
+    // The rest of the array is zeroed
+    assert_eq!(&bytes[11..], &[0; 7]);
+
+    let deserialized: Message =
+        binver::deserialize_slice_with_version(&bytes, Version::new(0, 0, 2)).unwrap();
+    assert_eq!(deserialized, message);
+}
@@ -0,0 +1,97 @@
+use binver::{
+    ByteOrder, IntegerEncoding, ReadConfig, ReadError, Serializable, Version, WriteConfig,
+};
+
+#[derive(Serializable, Debug, PartialEq, Default)]
+pub struct Buffer {
+    #[since(0.0.1)]
+    pub len: usize,
+    #[since(0.0.1)]
+    pub offset: isize,
+}
+
+const BUFFER: Buffer = Buffer { len: 5, offset: -1 };
+
+#[test]
+fn test_pointer_sized_are_64_bit() {
+    let mut slice = [0u8; 64];
+    let length = binver::write_to_slice(&mut slice, &BUFFER).unwrap();
+    assert_eq!(
+        &slice[6..length],
+        &[
+            0, 0, 0, 0, 0, 0, 0, 5, // len
+            0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, // offset
+        ]
+    );
+    let deserialized: Buffer = binver::deserialize_slice(&slice[..length]).unwrap();
+    assert_eq!(deserialized, BUFFER);
+    assert_eq!(usize::MAX_SERIALIZED_SIZE, u64::MAX_SERIALIZED_SIZE);
+}
+
+#[test]
+fn test_pointer_sized_options() {
+    let config = WriteConfig {
+        integer_encoding: IntegerEncoding::Varint,
+        ..WriteConfig::default()
+    };
+    let mut slice = [0u8; 64];
+    let length = binver::write_to_slice_with_config(&mut slice, &BUFFER, config).unwrap();
+    assert_eq!(&slice[6..length], &[5, 1]);
+
+    let config = WriteConfig {
+        byte_order: ByteOrder::LittleEndian,
+        ..WriteConfig::default()
+    };
+    let length = binver::write_to_slice_with_config(&mut slice, &BUFFER, config).unwrap();
+    assert_eq!(&slice[6..14], &[5, 0, 0, 0, 0, 0, 0, 0]);
+    let deserialized: Buffer = binver::deserialize_slice(&slice[..length]).unwrap();
+    assert_eq!(deserialized, BUFFER);
+}
+
+#[test]
+fn test_read_revision_2() {
+    // Revision 2 wrote pointer-sized integers with the width of the platform, which is 64 bits on the platforms this test runs on
+    #[cfg(target_pointer_width = "64")]
+    {
+        let mut data = vec![2, 0, 0, 1, 0, 0];
+        data.extend_from_slice(&7u64.to_be_bytes());
+        data.extend_from_slice(&(-2i64).to_be_bytes());
+        let deserialized: Buffer = binver::deserialize_slice(&data).unwrap();
+        assert_eq!(deserialized, Buffer { len: 7, offset: -2 });
+    }
+}
+
+#[test]
+fn test_write_revision_2() {
+    let config = WriteConfig {
+        schema_version: Some(Version::new(0, 0, 1)),
+        wire_revision: Some(2),
+        ..WriteConfig::default()
+    };
+    let mut slice = [0u8; 64];
+    let length = binver::write_to_slice_with_config(&mut slice, &BUFFER, config).unwrap();
+    assert_eq!(slice[0], 2);
+    assert_eq!(
+        length,
+        6 + 2 * core::mem::size_of::<usize>(),
+        "revision 2 uses the width of the platform"
+    );
+    let deserialized: Buffer = binver::deserialize_slice(&slice[..length]).unwrap();
+    assert_eq!(deserialized, BUFFER);
+}
+
+#[test]
+fn test_out_of_range() {
+    let mut data = vec![3, 0, 0, 1, 0, 0];
+    data.extend_from_slice(&u64::MAX.to_be_bytes());
+    data.extend_from_slice(&0i64.to_be_bytes());
+    let result = binver::deserialize_slice_with_config::<Buffer>(&data, ReadConfig::default());
+    if usize::BITS < 64 {
+        assert!(matches!(
+            result,
+            Err(ReadError::IntegerOutOfRange(value)) if value == u64::MAX as i128
+        ));
+    } else {
+        assert_eq!(result.unwrap().len, usize::MAX);
+    }
+}
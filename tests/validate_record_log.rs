@@ -0,0 +1,210 @@
+#![cfg(feature = "std")]
+
+use binver::{
+    record_log::{RecordLogReader, RecordLogWriter},
+    ReadError, Serializable, Version,
+};
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct Event {
+    #[since(0.0.1)]
+    pub id: u32,
+    #[since(0.0.2)]
+    pub name: String,
+}
+
+fn event(id: u32) -> Event {
+    Event {
+        id,
+        name: format!("event {}", id),
+    }
+}
+
+fn read_all(log: &[u8]) -> Vec<Event> {
+    RecordLogReader::new(log).collect::<Result<_, _>>().unwrap()
+}
+
+#[test]
+fn test_record_log_single_version_entry() {
+    let mut writer = RecordLogWriter::new(Vec::new());
+    for id in 0..3 {
+        writer.append(&event(id)).unwrap();
+    }
+    let log = writer.into_inner();
+
+    // kind + length + version, then kind + length + record for every event
+    assert_eq!(log[0], 0);
+    assert_eq!(&log[1..5], &6u32.to_be_bytes());
+    assert_eq!(log[11], 1);
+    assert_eq!(log.len(), 11 + 3 * (5 + 4 + 4 + 7));
+
+    assert_eq!(read_all(&log), vec![event(0), event(1), event(2)]);
+}
+
+#[test]
+fn test_record_log_mixed_versions() {
+    // An older version of the application wrote a record with only an `id`
+    let mut log = vec![0, 0, 0, 0, 6, 0, 0, 0, 0, 0, 1];
+    log.extend_from_slice(&[1, 0, 0, 0, 4]);
+    log.extend_from_slice(&7u32.to_be_bytes());
+
+    let mut writer = RecordLogWriter::resume(log, Some(Version::new(0, 0, 1)));
+    writer.append(&event(8)).unwrap();
+    let log = writer.into_inner();
+
+    assert_eq!(
+        read_all(&log),
+        vec![
+            Event {
+                id: 7,
+                name: String::new()
+            },
+            event(8)
+        ]
+    );
+}
+
+/// The `Event` of an older version of the application, before `name` was added.
+mod v1 {
+    use binver::Serializable;
+
+    #[derive(Serializable)]
+    pub struct Event {
+        #[since(0.0.1)]
+        pub id: u32,
+    }
+}
+
+#[test]
+fn test_record_log_with_version() {
+    let mut writer = RecordLogWriter::new(Vec::new()).with_version(Version::new(0, 0, 1));
+    writer.append(&v1::Event { id: 1 }).unwrap();
+    writer.append(&v1::Event { id: 2 }).unwrap();
+    let log = writer.into_inner();
+    // A single version entry, followed by two records with only an `id`
+    assert_eq!(log.len(), 11 + 2 * (5 + 4));
+
+    // After an upgrade, the application appends records with a newer version
+    let mut writer = RecordLogWriter::resume(log, Some(Version::new(0, 0, 1)))
+        .with_version(Version::new(0, 0, 2));
+    writer.append(&event(3)).unwrap();
+    let log = writer.into_inner();
+
+    let mut reader = RecordLogReader::new(&log[..]);
+    let events: Vec<Event> = reader.by_ref().collect::<Result<_, _>>().unwrap();
+    assert_eq!(
+        events,
+        vec![
+            Event {
+                id: 1,
+                name: String::new()
+            },
+            Event {
+                id: 2,
+                name: String::new()
+            },
+            event(3)
+        ]
+    );
+    assert_eq!(reader.last_version(), Some(&Version::new(0, 0, 2)));
+}
+
+#[test]
+fn test_record_log_torn_record() {
+    let mut writer = RecordLogWriter::new(Vec::new());
+    writer.append(&event(0)).unwrap();
+    writer.append(&event(1)).unwrap();
+    let mut log = writer.into_inner();
+    let complete_len = log.len() as u64;
+    log.truncate(log.len() - 3);
+
+    let mut reader = RecordLogReader::<_, Event>::new(&log[..]);
+    assert_eq!(reader.next().unwrap().unwrap(), event(0));
+    assert!(reader.next().is_none());
+    assert!(reader.is_torn());
+    assert_eq!(reader.valid_len(), complete_len - (5 + 4 + 4 + 7));
+}
+
+#[test]
+fn test_record_log_missing_version() {
+    let log = [1, 0, 0, 0, 4, 0, 0, 0, 1];
+    let mut reader = RecordLogReader::<_, Event>::new(&log[..]);
+    assert!(matches!(
+        reader.next(),
+        Some(Err(ReadError::MissingRecordVersion))
+    ));
+}
+
+#[test]
+fn test_record_log_open_file() {
+    let path = std::env::temp_dir().join(format!("binver_record_log_{}", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let mut writer = RecordLogWriter::open(&path).unwrap();
+    writer.append(&event(0)).unwrap();
+    writer.append(&event(1)).unwrap();
+    drop(writer);
+
+    // Simulate a crash halfway through writing a record
+    let len = std::fs::metadata(&path).unwrap().len();
+    std::fs::OpenOptions::new()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_len(len - 2)
+        .unwrap();
+
+    let mut writer = RecordLogWriter::open(&path).unwrap();
+    writer.append(&event(2)).unwrap();
+    drop(writer);
+
+    let log = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(read_all(&log), vec![event(0), event(2)]);
+    // Reopening the log does not write a second version entry
+    assert_eq!(log.len(), 11 + 2 * (5 + 4 + 4 + 7));
+}
+
+/// The kinds of the entries in the given log.
+fn entry_kinds(mut log: &[u8]) -> Vec<u8> {
+    let mut kinds = Vec::new();
+    while !log.is_empty() {
+        let len = u32::from_be_bytes([log[1], log[2], log[3], log[4]]) as usize;
+        kinds.push(log[0]);
+        log = &log[5 + len..];
+    }
+    kinds
+}
+
+#[test]
+fn test_record_log_with_config() {
+    use binver::{ByteOrder, IntegerEncoding, WriteConfig};
+
+    let config = WriteConfig {
+        schema_version: Some(Version::new(0, 0, 2)),
+        integer_encoding: IntegerEncoding::Varint,
+        byte_order: ByteOrder::LittleEndian,
+        features: &["pets"],
+        ..WriteConfig::default()
+    };
+    let mut writer = RecordLogWriter::new(Vec::new()).with_config(config);
+    writer.append(&event(1)).unwrap();
+    writer.append(&event(2)).unwrap();
+
+    // A different header writes a new version entry
+    let mut writer = writer.with_config(WriteConfig {
+        schema_version: Some(Version::new(0, 0, 2)),
+        ..WriteConfig::default()
+    });
+    writer.append(&event(3)).unwrap();
+    let log = writer.into_inner();
+    assert_eq!(entry_kinds(&log), vec![0, 1, 1, 0, 1]);
+
+    // Revision 3 with the feature, varint and little-endian flags
+    assert_eq!(log[5], 0x73);
+    // The first record is written with varints
+    let record = 5 + u32::from_be_bytes([log[1], log[2], log[3], log[4]]) as usize;
+    assert_eq!(&log[record..record + 7], &[1, 0, 0, 0, 9, 1, 7]);
+
+    assert_eq!(read_all(&log), vec![event(1), event(2), event(3)]);
+}
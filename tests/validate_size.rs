@@ -0,0 +1,58 @@
+use binver::{Serializable, Version, WriteConfig};
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct Packet<'a> {
+    #[since(0.0.1)]
+    pub id: u32,
+    #[since(0.0.1)]
+    pub name: &'a str,
+    #[since(0.0.1)]
+    pub payload: &'a [u8],
+}
+
+const PACKET: Packet = Packet {
+    id: 1,
+    name: "ping",
+    payload: &[1, 2, 3, 4, 5],
+};
+
+#[test]
+fn test_serialized_size() {
+    let size = binver::serialized_size(&PACKET).unwrap();
+    assert_eq!(size, 6 + 4 + 4 + 4 + 4 + 5);
+    assert_eq!(
+        binver::serialized_size_without_header(&PACKET).unwrap(),
+        size - 6
+    );
+
+    // A buffer of exactly the serialized size is large enough
+    let mut slice = [0u8; 27];
+    assert_eq!(binver::write_to_slice(&mut slice, &PACKET).unwrap(), size);
+    assert!(binver::write_to_slice(&mut slice[..size - 1], &PACKET).is_err());
+}
+
+#[test]
+fn test_serialized_size_with_config() {
+    let config = WriteConfig {
+        magic: Some(binver::MAGIC),
+        schema_version: Some(Version::parse("1.0.0-alpha").unwrap()),
+        type_fingerprint: true,
+        ..WriteConfig::default()
+    };
+    let size = binver::serialized_size_with_config(&PACKET, config.clone()).unwrap();
+    let mut slice = [0u8; 64];
+    let length = binver::write_to_slice_with_config(&mut slice, &PACKET, config).unwrap();
+    assert_eq!(size, length);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_to_vec_capacity() {
+    let serialized = binver::to_vec(&PACKET);
+    assert_eq!(serialized.len(), binver::serialized_size(&PACKET).unwrap());
+
+    // Types with a maximum size are preallocated with that size
+    let serialized = binver::to_vec_without_header(&(1u32, 2u64)).unwrap();
+    assert_eq!(serialized.len(), 12);
+    assert_eq!(serialized.capacity(), 12);
+}
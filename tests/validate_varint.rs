@@ -0,0 +1,196 @@
+#![cfg(feature = "std")]
+
+use binver::{IntegerEncoding, ReadError, Serializable, Version, WriteConfig, WriteError};
+
+#[derive(Serializable, Debug, PartialEq, Default)]
+pub struct Player {
+    #[since(0.0.1)]
+    pub id: u64,
+    #[since(0.0.1)]
+    pub offset: i32,
+    #[since(0.0.1)]
+    pub level: u8,
+    #[since(0.0.1)]
+    pub name: String,
+    #[since(0.0.1)]
+    pub scores: Vec<u32>,
+}
+
+#[derive(Serializable, Debug, PartialEq, Default)]
+pub struct Entity {
+    #[since(0.0.1)]
+    #[binver(varint)]
+    pub id: u64,
+    #[since(0.0.1)]
+    pub health: u16,
+    #[since(0.0.1)]
+    #[binver(varint)]
+    pub path: Vec<i64>,
+}
+
+fn varint_config() -> WriteConfig<'static> {
+    WriteConfig {
+        schema_version: Some(Version::new(0, 0, 1)),
+        integer_encoding: IntegerEncoding::Varint,
+        ..WriteConfig::default()
+    }
+}
+
+#[test]
+fn test_document_varint() {
+    let player = Player {
+        id: 300,
+        offset: -3,
+        level: 200,
+        name: "Trangar".to_owned(),
+        scores: vec![1, 128],
+    };
+    let serialized = binver::to_vec_with_config(&player, varint_config());
+    assert_eq!(
+        serialized,
+        vec![
+            0x23, 0, 0, 1, 0, 0, // revision with the varint flag, version 0.0.1
+            0xAC, 0x02, // id
+            5,    // offset, zig-zag encoded
+            200,  // level, single bytes are not varints
+            7, b'T', b'r', b'a', b'n', b'g', b'a', b'r', // name
+            2, 1, 0x80, 0x01, // scores
+        ]
+    );
+    assert_eq!(
+        binver::serialized_size_with_config(&player, varint_config()).unwrap(),
+        serialized.len()
+    );
+    let deserialized: Player = binver::deserialize_slice(&serialized).unwrap();
+    assert_eq!(deserialized, player);
+}
+
+#[test]
+fn test_fixed_data_still_decodes() {
+    let player = Player {
+        id: 300,
+        offset: -3,
+        level: 1,
+        name: "Trangar".to_owned(),
+        scores: vec![1, 128],
+    };
+    let serialized = binver::to_vec(&player);
+    assert_eq!(&serialized[6..14], &300u64.to_be_bytes());
+    let deserialized: Player = binver::deserialize_slice(&serialized).unwrap();
+    assert_eq!(deserialized, player);
+}
+
+#[test]
+fn test_extreme_values() {
+    #[derive(Serializable, Debug, PartialEq, Default)]
+    pub struct Extremes {
+        #[since(0.0.1)]
+        pub a: u128,
+        #[since(0.0.1)]
+        pub b: i64,
+        #[since(0.0.1)]
+        pub c: i16,
+        #[since(0.0.1)]
+        pub d: usize,
+    }
+    let extremes = Extremes {
+        a: u128::MAX,
+        b: i64::MIN,
+        c: i16::MAX,
+        d: usize::MAX,
+    };
+    let serialized = binver::to_vec_with_config(&extremes, varint_config());
+    let deserialized: Extremes = binver::deserialize_slice(&serialized).unwrap();
+    assert_eq!(deserialized, extremes);
+}
+
+#[test]
+fn test_field_varint() {
+    let entity = Entity {
+        id: 5,
+        health: 100,
+        path: vec![-1, 1],
+    };
+    let serialized = binver::to_vec(&entity);
+    assert_eq!(
+        &serialized[6..],
+        &[
+            5, // id
+            0, 100, // health is not a varint
+            2, 1, 2, // path, including its length
+        ]
+    );
+    let deserialized: Entity = binver::deserialize_slice(&serialized).unwrap();
+    assert_eq!(deserialized, entity);
+}
+
+mod fixed {
+    use binver::Serializable;
+
+    #[derive(Serializable)]
+    pub struct Id {
+        #[since(0.0.1)]
+        pub id: u64,
+    }
+}
+
+mod varint {
+    use binver::Serializable;
+
+    #[derive(Serializable)]
+    pub struct Id {
+        #[since(0.0.1)]
+        #[binver(varint)]
+        pub id: u64,
+    }
+}
+
+#[test]
+fn test_field_varint_changes_fingerprint() {
+    assert_ne!(fixed::Id::FINGERPRINT, varint::Id::FINGERPRINT);
+}
+
+#[test]
+fn test_invalid_varint() {
+    let mut data = vec![0x23, 0, 0, 1, 0, 0];
+    // 2^16 does not fit in a `u16`
+    data.extend_from_slice(&[0x80, 0x80, 0x04]);
+    assert!(matches!(
+        binver::deserialize_slice::<u16>(&data),
+        Err(ReadError::InvalidVarint)
+    ));
+}
+
+#[test]
+fn test_varint_requires_revision_2() {
+    let config = WriteConfig {
+        wire_revision: Some(1),
+        ..varint_config()
+    };
+    let mut slice = [0u8; 64];
+    assert!(matches!(
+        binver::write_to_slice_with_config(&mut slice, &5u32, config),
+        Err(WriteError::UnsupportedWireRevision(1))
+    ));
+}
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct Position {
+    #[since(0.0.1)]
+    #[binver(varint)]
+    pub x: u64,
+    #[since(0.0.1)]
+    pub y: u16,
+}
+
+#[test]
+fn test_field_varint_max_size() {
+    // The varint field is bounded by its largest varint, the other field keeps its fixed size
+    assert_eq!(Position::MAX_SERIALIZED_SIZE, Some(12 + 2));
+
+    let position = Position { x: u64::MAX, y: 3 };
+    let bytes: [u8; 14] = binver::encode_to_array(&position).unwrap();
+    let deserialized: Position =
+        binver::deserialize_slice_with_version(&bytes, Version::new(0, 0, 1)).unwrap();
+    assert_eq!(deserialized, position);
+}
@@ -0,0 +1,79 @@
+use binver::{ReadError, Version, WriteConfig, WriteError};
+
+fn write_version(version: &Version) -> ([u8; 64], usize) {
+    let mut slice = [0u8; 64];
+    let config = WriteConfig {
+        schema_version: Some(version.clone()),
+        ..WriteConfig::default()
+    };
+    let length = binver::write_to_slice_with_config(&mut slice, version, config).unwrap();
+    (slice, length)
+}
+
+#[test]
+fn test_pre_release_and_build_metadata() {
+    let version = Version::parse("1.0.0-beta.2+build.5").unwrap();
+    let (slice, length) = write_version(&version);
+
+    let header = binver::sniff_with_magic(&slice[..length], &[]).unwrap();
+    assert_eq!(header, version);
+    assert_ne!(header, Version::new(1, 0, 0));
+    let deserialized: Version = binver::deserialize_slice(&slice[..length]).unwrap();
+    assert_eq!(deserialized, version);
+}
+
+#[test]
+fn test_large_components() {
+    let version = Version::new(u64::MAX, 70_000, 20_240_101);
+    let (slice, length) = write_version(&version);
+
+    let deserialized: Version = binver::deserialize_slice(&slice[..length]).unwrap();
+    assert_eq!(deserialized, version);
+}
+
+#[test]
+fn test_older_revision_out_of_range() {
+    let mut slice = [0u8; 64];
+    for version in [
+        Version::new(70_000, 0, 0),
+        Version::parse("1.0.0-rc.1").unwrap(),
+    ] {
+        let config = WriteConfig {
+            wire_revision: Some(1),
+            ..WriteConfig::default()
+        };
+        assert!(matches!(
+            binver::write_to_slice_with_config(&mut slice, &version, config),
+            Err(WriteError::VersionOutOfRange(v)) if v == version
+        ));
+    }
+}
+
+#[test]
+fn test_invalid_version() {
+    // Revision 2 header for 0.0.1 with the pre-release identifier "+"
+    let bytes = [2, 0, 0, 1, 1, b'+', 0];
+    assert!(matches!(
+        binver::deserialize_slice::<Version>(&bytes),
+        Err(ReadError::InvalidVersion)
+    ));
+
+    let overlong = [
+        2, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x7F,
+    ];
+    assert!(matches!(
+        binver::deserialize_slice::<Version>(&overlong),
+        Err(ReadError::InvalidVarint)
+    ));
+}
+
+#[test]
+fn test_huge_identifier_length() {
+    // The length of the pre-release identifier is larger than the data, which must not be allocated
+    let bytes = b"BINVER\x03\0\0\0\xff\xff\xff\xff\xff\xff\xff\xff\xff\x01";
+    assert_eq!(binver::sniff(bytes), None);
+    assert!(matches!(
+        binver::deserialize_slice::<u8>(&bytes[6..]),
+        Err(ReadError::EndOfInput)
+    ));
+}
@@ -0,0 +1,206 @@
+use binver::{ReadError, Serializable, Version, Versioned};
+
+#[derive(Serializable, Debug, PartialEq, Default)]
+pub struct PluginData {
+    #[since(1.0.0)]
+    pub enabled: bool,
+    #[since(1.1.0)]
+    pub level: u16,
+}
+
+#[derive(Serializable, Debug, PartialEq, Default)]
+pub struct SaveFile {
+    #[since(0.0.1)]
+    pub seed: u32,
+    #[since(0.0.1)]
+    pub plugin: Versioned<PluginData>,
+}
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct Pair {
+    #[since(0.0.1)]
+    pub save: SaveFile,
+    #[since(0.0.1)]
+    pub tail: u32,
+}
+
+#[test]
+fn test_versioned_roundtrip() {
+    let save = SaveFile {
+        seed: 42,
+        plugin: Versioned::new(
+            Version::new(1, 1, 0),
+            PluginData {
+                enabled: true,
+                level: 3,
+            },
+        ),
+    };
+    let mut slice = [0u8; 64];
+    let length = binver::write_to_slice(&mut slice, &save).unwrap();
+    assert_eq!(
+        &slice[6..length],
+        &[
+            0,
+            0,
+            0,
+            42, // seed
+            0,
+            0,
+            0,
+            9, // sub-document length
+            binver::WIRE_REVISION,
+            1,
+            1,
+            0,
+            0,
+            0, // sub-document header
+            1,
+            0,
+            3, // plugin data
+        ]
+    );
+
+    // The document version is lower than any `since` of the plugin data, but the sub-document version is used
+    let deserialized: SaveFile = binver::deserialize_slice(&slice[..length]).unwrap();
+    assert_eq!(deserialized, save);
+}
+
+#[test]
+fn test_versioned_older_version() {
+    let bytes = [
+        2, 0, 0, 1, 0, 0, // document header, version 0.0.1
+        0, 0, 0, 42, // seed
+        0, 0, 0, 7, // sub-document length
+        2, 1, 0, 0, 0, 0, // sub-document header, version 1.0.0
+        1, // plugin data, without level
+    ];
+    let deserialized: SaveFile = binver::deserialize_slice(&bytes).unwrap();
+    assert_eq!(deserialized.plugin.version, Version::new(1, 0, 0));
+    assert_eq!(
+        deserialized.plugin.value,
+        PluginData {
+            enabled: true,
+            level: 0
+        }
+    );
+}
+
+#[test]
+fn test_versioned_skips_unknown_fields() {
+    let bytes = [
+        2, 0, 0, 1, 0, 0, // document header, version 0.0.1
+        0, 0, 0, 42, // seed
+        0, 0, 0, 12, // sub-document length
+        2, 2, 0, 0, 0, 0, // sub-document header, version 2.0.0
+        1, 0, 3, // plugin data
+        9, 9, 9, // fields that were added in 2.0.0
+    ];
+    let deserialized: Versioned<PluginData> = binver::deserialize_slice(
+        &bytes[..6]
+            .iter()
+            .chain(&bytes[10..])
+            .copied()
+            .collect::<Vec<u8>>(),
+    )
+    .unwrap();
+    assert_eq!(deserialized.version, Version::new(2, 0, 0));
+    assert_eq!(
+        deserialized.value,
+        PluginData {
+            enabled: true,
+            level: 3
+        }
+    );
+
+    let deserialized: SaveFile = binver::deserialize_slice(&bytes).unwrap();
+    assert_eq!(deserialized.seed, 42);
+}
+
+#[test]
+fn test_versioned_truncated() {
+    let bytes = [
+        2, 0, 0, 1, 0, 0, // document header, version 0.0.1
+        0, 0, 0, 42, // seed
+        0, 0, 0, 6, // sub-document length, too short for the plugin data
+        2, 1, 1, 0, 0, 0, // sub-document header, version 1.1.0
+        1, 0, 3, // plugin data
+    ];
+    assert!(matches!(
+        binver::deserialize_slice::<SaveFile>(&bytes),
+        Err(ReadError::EndOfInput)
+    ));
+}
+
+/// Counts how often it is serialized.
+struct Counted;
+
+static SERIALIZED: core::sync::atomic::AtomicUsize = core::sync::atomic::AtomicUsize::new(0);
+
+impl<'a> Serializable<'a> for Counted {
+    fn serialize<W: binver::Writer + ?Sized>(&self, writer: &mut W) -> binver::WriteResult {
+        SERIALIZED.fetch_add(1, core::sync::atomic::Ordering::SeqCst);
+        300u32.serialize(writer)
+    }
+
+    fn deserialize<R: binver::Reader<'a> + ?Sized>(reader: &mut R) -> binver::ReadResult<Self> {
+        assert_eq!(u32::deserialize(reader)?, 300);
+        Ok(Counted)
+    }
+}
+
+type Nested = Versioned<Versioned<Versioned<Versioned<Counted>>>>;
+
+fn nested() -> Nested {
+    let version = Version::new(1, 0, 0);
+    Versioned::new(
+        version.clone(),
+        Versioned::new(
+            version.clone(),
+            Versioned::new(version.clone(), Versioned::new(version, Counted)),
+        ),
+    )
+}
+
+#[test]
+fn test_nested_versioned_is_serialized_once() {
+    use binver::{IntegerEncoding, WriteConfig};
+
+    let config = WriteConfig {
+        integer_encoding: IntegerEncoding::Varint,
+        ..WriteConfig::default()
+    };
+    let mut slice = [0u8; 64];
+    SERIALIZED.store(0, core::sync::atomic::Ordering::SeqCst);
+    let length = binver::write_to_slice_with_config(&mut slice, &nested(), config).unwrap();
+    assert_eq!(SERIALIZED.load(core::sync::atomic::Ordering::SeqCst), 1);
+    binver::deserialize_slice::<Nested>(&slice[..length]).unwrap();
+
+    // The lengths are inserted before the sub-documents, so they must still fit in the slice
+    let length = binver::write_to_slice(&mut slice, &nested()).unwrap();
+    assert!(matches!(
+        binver::write_to_slice(&mut slice[..length - 1], &nested()),
+        Err(binver::WriteError::EndOfOutput)
+    ));
+
+    #[cfg(feature = "std")]
+    {
+        /// A writer that does not support `Writer::insert`.
+        struct Unseekable(Vec<u8>);
+
+        impl binver::Writer for Unseekable {
+            fn write(&mut self, bytes: &[u8]) -> binver::WriteResult {
+                self.0.extend_from_slice(bytes);
+                Ok(())
+            }
+        }
+
+        SERIALIZED.store(0, core::sync::atomic::Ordering::SeqCst);
+        let serialized = binver::to_vec(&nested());
+        let mut unseekable = Unseekable(Vec::new());
+        nested().serialize(&mut unseekable).unwrap();
+        assert_eq!(SERIALIZED.load(core::sync::atomic::Ordering::SeqCst), 2);
+        // Without the version header, both writers write the same bytes
+        assert!(serialized.ends_with(&unseekable.0));
+    }
+}
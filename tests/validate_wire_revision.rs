@@ -0,0 +1,93 @@
+use binver::{ReadError, Serializable, Version, WriteConfig, WriteError};
+
+#[derive(Serializable, Debug, PartialEq)]
+pub struct Test {
+    #[since(0.0.1)]
+    pub id: u32,
+    #[since(0.0.2)]
+    pub level: u16,
+}
+
+#[test]
+fn test_header_contains_wire_revision() {
+    let mut slice = [0u8; 64];
+    let length = binver::write_to_slice(&mut slice, &Test { id: 1, level: 2 }).unwrap();
+    assert_eq!(slice[0], binver::WIRE_REVISION);
+    assert_eq!(slice[0], 3);
+
+    let config = WriteConfig {
+        schema_version: Some(Version::new(1, 2, 3)),
+        ..WriteConfig::default()
+    };
+    let length2 =
+        binver::write_to_slice_with_config(&mut slice[length..], &Test { id: 1, level: 2 }, config)
+            .unwrap();
+    assert_eq!(
+        &slice[length..][..length2],
+        &[3, 1, 2, 3, 0, 0, 0, 0, 0, 1, 0, 2]
+    );
+    let deserialized: Test = binver::deserialize_slice(&slice[length..][..length2]).unwrap();
+    assert_eq!(deserialized, Test { id: 1, level: 2 });
+}
+
+#[test]
+fn test_read_revision_0() {
+    // Before the wire revision was added, the header started directly with the version
+    let legacy = [0, 0, 0, 0, 0, 1, 0, 0, 0, 5];
+    let deserialized: Test = binver::deserialize_slice(&legacy).unwrap();
+    assert_eq!(deserialized, Test { id: 5, level: 0 });
+}
+
+#[test]
+fn test_read_revision_1() {
+    // Revision 1 encoded the version as 3 `u16`s
+    let legacy = [1, 0, 0, 0, 0, 0, 1, 0, 0, 0, 5];
+    let deserialized: Test = binver::deserialize_slice(&legacy).unwrap();
+    assert_eq!(deserialized, Test { id: 5, level: 0 });
+}
+
+#[test]
+fn test_write_older_revision() {
+    let mut slice = [0u8; 64];
+    let config = WriteConfig {
+        schema_version: Some(Version::new(1, 2, 3)),
+        wire_revision: Some(1),
+        ..WriteConfig::default()
+    };
+    let length =
+        binver::write_to_slice_with_config(&mut slice, &Test { id: 1, level: 2 }, config).unwrap();
+    assert_eq!(&slice[..length], &[1, 0, 1, 0, 2, 0, 3, 0, 0, 0, 1, 0, 2]);
+    let deserialized: Test = binver::deserialize_slice(&slice[..length]).unwrap();
+    assert_eq!(deserialized, Test { id: 1, level: 2 });
+
+    let config = WriteConfig {
+        wire_revision: Some(binver::WIRE_REVISION + 1),
+        ..WriteConfig::default()
+    };
+    assert!(matches!(
+        binver::write_to_slice_with_config(&mut slice, &Test { id: 1, level: 2 }, config),
+        Err(WriteError::UnsupportedWireRevision(4))
+    ));
+}
+
+#[test]
+fn test_unsupported_revision() {
+    let future = [binver::WIRE_REVISION + 1, 0, 0, 0, 0, 0, 1, 0, 0, 0, 5];
+    assert!(matches!(
+        binver::deserialize_slice::<Test>(&future),
+        Err(ReadError::UnsupportedWireRevision(4))
+    ));
+}
+
+#[test]
+fn test_compressed_flag() {
+    // Revision 3 with the compressed flag, version 0.0.1, and a `u8` stored without compression
+    let data = [0x0B, 0, 0, 1, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 1, 42];
+    #[cfg(feature = "compression")]
+    assert_eq!(binver::deserialize_slice::<u8>(&data).unwrap(), 42);
+    #[cfg(not(feature = "compression"))]
+    assert!(matches!(
+        binver::deserialize_slice::<u8>(&data),
+        Err(ReadError::UnknownCodec(0))
+    ));
+}